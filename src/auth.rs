@@ -1,5 +1,22 @@
 //! Credentials management, for access to the Docker Hub or a custom Registry.
 
+use super::Docker;
+use crate::context::docker_config_dir;
+use crate::errors::Error;
+use crate::errors::Error::*;
+
+use http::header::HeaderValue;
+use hyper::{Body, Request};
+use serde::ser::Serialize;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{Output, Stdio};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[allow(missing_docs)]
 /// DockerCredentials credentials and server URI to push images using the [Push Image
@@ -18,3 +35,626 @@ pub struct DockerCredentials {
 pub(crate) fn base64_url_encode(payload: &str) -> String {
     base64::encode_config(payload, base64::URL_SAFE)
 }
+
+/// Set a header carrying base64 encoded registry credentials, such as `X-Registry-Auth`, on a
+/// built request.
+pub(crate) fn with_registry_header<C>(
+    request: Result<Request<Body>, Error>,
+    header: &'static str,
+    credentials: &C,
+) -> Result<Request<Body>, Error>
+where
+    C: Serialize,
+{
+    let mut request = request?;
+    let value = base64_url_encode(&serde_json::to_string(credentials)?);
+    request.headers_mut().insert(
+        header,
+        HeaderValue::from_str(&value).map_err(http::Error::from)?,
+    );
+    Ok(request)
+}
+
+/// Registry that image references without a registry hostname are pulled from.
+const DEFAULT_REGISTRY: &str = "docker.io";
+
+/// Server address the docker CLI stores Docker Hub credentials under.
+const DEFAULT_SERVER_ADDRESS: &str = "https://index.docker.io/v1/";
+
+/// Message printed by credential helpers that hold no credentials for a server.
+const CREDENTIALS_NOT_FOUND: &str = "credentials not found in native keychain";
+
+/// Credentials stored inline in the `auths` section of the docker CLI configuration file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[allow(missing_docs)]
+pub struct DockerConfigAuth {
+    pub auth: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub email: Option<String>,
+    pub identitytoken: Option<String>,
+    pub registrytoken: Option<String>,
+}
+
+/// The registry credentials configured for the docker CLI in `config.json`, found in the
+/// directory named by the `DOCKER_CONFIG` environment variable, or `~/.docker`.
+///
+/// Credentials are resolved the same way as the docker CLI: a registry specific helper in
+/// `credHelpers` takes precedence over the `credsStore` helper, which takes precedence over the
+/// `auths` section. Helpers are run as `docker-credential-<helper>` programs.
+///
+/// Reading this file is opt-in: by default, calls that accept credentials, such as
+/// [`create_image`](crate::Docker::create_image()),
+/// [`push_image`](crate::Docker::push_image()) and
+/// [`build_image`](crate::Docker::build_image()), send only the credentials they are given.
+/// Resolve them with this type and pass them in explicitly, or configure the client with
+/// [`DockerBuilder::docker_config`](crate::DockerBuilder::docker_config()) to fall back to this
+/// file for calls made without credentials.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// # use bollard::Docker;
+/// # let docker = Docker::connect_with_http_defaults().unwrap();
+/// use bollard::auth::DockerConfigFile;
+/// use bollard::image::CreateImageOptions;
+///
+/// use futures_util::stream::TryStreamExt;
+///
+/// async move {
+///     let config = DockerConfigFile::load()?;
+///     let credentials = config.credentials_for_image("ghcr.io/owner/image:latest").await?;
+///
+///     let options = Some(CreateImageOptions {
+///         from_image: "ghcr.io/owner/image:latest",
+///         ..Default::default()
+///     });
+///
+///     docker.create_image(options, None, credentials).try_collect::<Vec<_>>().await
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DockerConfigFile {
+    /// Credentials stored inline, keyed by registry server address.
+    #[serde(default)]
+    pub auths: HashMap<String, DockerConfigAuth>,
+    /// Credential helper used for all registries without an entry in `cred_helpers`.
+    #[serde(rename = "credsStore", default)]
+    pub creds_store: Option<String>,
+    /// Credential helpers, keyed by registry hostname.
+    #[serde(rename = "credHelpers", default)]
+    pub cred_helpers: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CredentialHelperResponse {
+    username: String,
+    secret: String,
+}
+
+/// The registry hostname of an image reference, defaulting to the Docker Hub.
+fn registry_hostname(image: &str) -> &str {
+    match image.split_once('/') {
+        Some((host, _)) if host.contains('.') || host.contains(':') || host == "localhost" => host,
+        _ => DEFAULT_REGISTRY,
+    }
+}
+
+/// Reduce a server address such as `https://registry.example.com/v1/` to its hostname.
+fn normalize_server_address(address: &str) -> &str {
+    let address = address
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let host = address.split('/').next().unwrap_or(address);
+    match host {
+        "index.docker.io" | "registry-1.docker.io" => DEFAULT_REGISTRY,
+        host => host,
+    }
+}
+
+/// The address credentials for a registry hostname are stored under.
+fn server_address(registry: &str) -> &str {
+    match registry {
+        DEFAULT_REGISTRY => DEFAULT_SERVER_ADDRESS,
+        registry => registry,
+    }
+}
+
+impl DockerConfigFile {
+    /// Read the docker CLI configuration file. A missing file is read as an empty
+    /// configuration.
+    pub fn load() -> Result<DockerConfigFile, Error> {
+        DockerConfigFile::from_path(&docker_config_dir()?.join("config.json"))
+    }
+
+    /// Read a docker CLI configuration file from the given path. A missing file is read as an
+    /// empty configuration.
+    pub fn from_path(path: &Path) -> Result<DockerConfigFile, Error> {
+        match fs::read(path) {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(DockerConfigFile::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Resolve the credentials for the registry of an image reference, such as
+    /// `registry.example.com/repository:tag`. References without a registry hostname resolve to
+    /// the Docker Hub.
+    pub async fn credentials_for_image(
+        &self,
+        image: &str,
+    ) -> Result<Option<DockerCredentials>, Error> {
+        self.credentials_for_registry(registry_hostname(image))
+            .await
+    }
+
+    /// Resolve the credentials for a registry hostname, such as `registry.example.com:5000`.
+    pub async fn credentials_for_registry(
+        &self,
+        registry: &str,
+    ) -> Result<Option<DockerCredentials>, Error> {
+        let registry = normalize_server_address(registry);
+        let address = server_address(registry);
+
+        if let Some(helper) = self.helper_for(registry, address) {
+            return credential_helper_get(helper, address).await;
+        }
+
+        Ok(self
+            .auths
+            .iter()
+            .find(|(key, _)| normalize_server_address(key) == registry)
+            .map(|(_, auth)| auth_credentials(auth, address)))
+    }
+
+    /// Resolve the credentials for every registry the configuration knows of, keyed by server
+    /// address, as expected by the [Build Image API](crate::Docker::build_image()).
+    pub async fn all_credentials(&self) -> Result<HashMap<String, DockerCredentials>, Error> {
+        let mut addresses: Vec<String> = self.auths.keys().cloned().collect();
+        addresses.extend(self.cred_helpers.keys().cloned());
+        if let Some(ref store) = self.creds_store {
+            addresses.extend(credential_helper_list(store).await?);
+        }
+
+        let mut credentials = HashMap::new();
+        for address in addresses {
+            if credentials.contains_key(&address) {
+                continue;
+            }
+            if let Some(resolved) = self.credentials_for_registry(&address).await? {
+                credentials.insert(address, resolved);
+            }
+        }
+
+        Ok(credentials)
+    }
+
+    fn helper_for(&self, registry: &str, address: &str) -> Option<&str> {
+        self.cred_helpers
+            .get(address)
+            .or_else(|| self.cred_helpers.get(registry))
+            .or(self.creds_store.as_ref())
+            .map(String::as_str)
+            .filter(|helper| !helper.is_empty())
+    }
+}
+
+fn auth_credentials(auth: &DockerConfigAuth, address: &str) -> DockerCredentials {
+    let mut credentials = DockerCredentials {
+        username: auth.username.clone(),
+        password: auth.password.clone(),
+        email: auth.email.clone(),
+        serveraddress: Some(address.to_string()),
+        identitytoken: auth.identitytoken.clone(),
+        registrytoken: auth.registrytoken.clone(),
+        ..Default::default()
+    };
+
+    // The CLI stores `username:password` base64 encoded, which the daemon expects split up.
+    if let Some(decoded) = auth
+        .auth
+        .as_ref()
+        .and_then(|auth| base64::decode(auth).ok())
+        .and_then(|decoded| String::from_utf8(decoded).ok())
+    {
+        if let Some((username, password)) = decoded.split_once(':') {
+            credentials.username = Some(username.to_string());
+            credentials.password = Some(password.to_string());
+        }
+    }
+
+    credentials
+}
+
+async fn credential_helper(helper: &str, action: &str, input: &str) -> Result<Output, Error> {
+    let program = format!("docker-credential-{}", helper);
+    let mut child = Command::new(&program)
+        .arg(action)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| CredentialHelperError {
+            helper: program.clone(),
+            message: e.to_string(),
+        })?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes()).await?;
+    }
+
+    Ok(child.wait_with_output().await?)
+}
+
+async fn credential_helper_get(
+    helper: &str,
+    address: &str,
+) -> Result<Option<DockerCredentials>, Error> {
+    let output = credential_helper(helper, "get", address).await?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if message == CREDENTIALS_NOT_FOUND {
+            return Ok(None);
+        }
+        return Err(CredentialHelperError {
+            helper: format!("docker-credential-{}", helper),
+            message,
+        });
+    }
+
+    let response: CredentialHelperResponse = serde_json::from_slice(&output.stdout)?;
+    let mut credentials = DockerCredentials {
+        serveraddress: Some(address.to_string()),
+        ..Default::default()
+    };
+
+    // Helpers store identity tokens with this placeholder username.
+    if response.username == "<token>" {
+        credentials.identitytoken = Some(response.secret);
+    } else {
+        credentials.username = Some(response.username);
+        credentials.password = Some(response.secret);
+    }
+
+    Ok(Some(credentials))
+}
+
+async fn credential_helper_list(helper: &str) -> Result<Vec<String>, Error> {
+    let output = credential_helper(helper, "list", "").await?;
+    if !output.status.success() {
+        return Err(CredentialHelperError {
+            helper: format!("docker-credential-{}", helper),
+            message: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        });
+    }
+
+    let addresses: HashMap<String, String> = serde_json::from_slice(&output.stdout)?;
+    Ok(addresses.into_keys().collect())
+}
+
+/// The value of a string field of query options, such as the image reference of a pull.
+pub(crate) fn query_field<O>(options: Option<&O>, field: &str) -> Option<String>
+where
+    O: Serialize,
+{
+    match serde_json::to_value(options?).ok()? {
+        serde_json::Value::Object(mut map) => match map.remove(field)? {
+            serde_json::Value::String(value) => Some(value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl Docker {
+    /// The credentials to send for an image when none were passed: those the
+    /// [DockerConfigFile] the client was built with holds for the registry of the image, if any.
+    pub(crate) async fn configured_credentials(
+        &self,
+        credentials: Option<DockerCredentials>,
+        image: Option<&str>,
+    ) -> Result<Option<DockerCredentials>, Error> {
+        match (credentials, &self.docker_config, image) {
+            (None, Some(config), Some(image)) => config.credentials_for_image(image).await,
+            (credentials, _, _) => Ok(credentials),
+        }
+    }
+
+    /// Fill in the identity token returned by a previous [Check Auth
+    /// API](crate::Docker::check_auth()) call, if the credentials carry no secret of their own.
+    /// The registry is taken from the server address of the credentials, or else from the image
+    /// reference the request is for.
+    pub(crate) fn resolve_registry_credentials(
+        &self,
+        credentials: Option<DockerCredentials>,
+        image: Option<&str>,
+    ) -> DockerCredentials {
+        let mut credentials = credentials.unwrap_or_default();
+
+        if credentials.password.is_some()
+            || credentials.auth.is_some()
+            || credentials.identitytoken.is_some()
+            || credentials.registrytoken.is_some()
+        {
+            return credentials;
+        }
+
+        let registry = match (&credentials.serveraddress, image) {
+            (Some(serveraddress), _) => normalize_server_address(serveraddress).to_string(),
+            (None, Some(image)) => registry_hostname(image).to_string(),
+            (None, None) => return credentials,
+        };
+
+        if let Ok(tokens) = self.identity_tokens.read() {
+            if let Some(identity_token) = tokens.get(&registry) {
+                credentials.identitytoken = Some(identity_token.clone());
+                credentials
+                    .serveraddress
+                    .get_or_insert_with(|| server_address(&registry).to_string());
+            }
+        }
+
+        credentials
+    }
+
+    pub(crate) fn store_identity_token(&self, serveraddress: &str, identity_token: &str) {
+        if let Ok(mut tokens) = self.identity_tokens.write() {
+            tokens.insert(
+                normalize_server_address(serveraddress).to_string(),
+                String::from(identity_token),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_registry_credentials() {
+        let docker = Docker::connect_with_http_defaults().unwrap();
+        docker.store_identity_token("localhost:5000", "token");
+
+        docker.store_identity_token("https://index.docker.io/v1/", "hub-token");
+
+        let credentials = docker.resolve_registry_credentials(
+            Some(DockerCredentials {
+                serveraddress: Some(String::from("http://localhost:5000/")),
+                ..Default::default()
+            }),
+            None,
+        );
+        assert_eq!(credentials.identitytoken, Some(String::from("token")));
+
+        let credentials = docker.resolve_registry_credentials(
+            Some(DockerCredentials {
+                serveraddress: Some(String::from("localhost:5000")),
+                password: Some(String::from("password")),
+                ..Default::default()
+            }),
+            None,
+        );
+        assert_eq!(credentials.identitytoken, None);
+
+        let credentials = docker.resolve_registry_credentials(None, Some("localhost:5000/image"));
+        assert_eq!(credentials.identitytoken, Some(String::from("token")));
+        assert_eq!(
+            credentials.serveraddress,
+            Some(String::from("localhost:5000"))
+        );
+
+        let credentials = docker.resolve_registry_credentials(None, Some("ubuntu:latest"));
+        assert_eq!(credentials.identitytoken, Some(String::from("hub-token")));
+        assert_eq!(
+            credentials.serveraddress,
+            Some(String::from("https://index.docker.io/v1/"))
+        );
+
+        let credentials = docker.resolve_registry_credentials(None, Some("ghcr.io/owner/image"));
+        assert_eq!(credentials.identitytoken, None);
+
+        let credentials = docker.resolve_registry_credentials(None, None);
+        assert_eq!(credentials.identitytoken, None);
+    }
+
+    #[test]
+    fn test_query_field() {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Options {
+            from_image: &'static str,
+        }
+
+        let options = Options {
+            from_image: "localhost:5000/image",
+        };
+        assert_eq!(
+            query_field(Some(&options), "fromImage"),
+            Some(String::from("localhost:5000/image"))
+        );
+        assert_eq!(query_field(Some(&options), "tag"), None);
+        assert_eq!(query_field(None::<&Options>, "fromImage"), None);
+    }
+
+    #[test]
+    fn test_registry_hostname() {
+        assert_eq!(registry_hostname("ubuntu:latest"), "docker.io");
+        assert_eq!(registry_hostname("library/ubuntu"), "docker.io");
+        assert_eq!(registry_hostname("localhost/image"), "localhost");
+        assert_eq!(
+            registry_hostname("localhost:5000/image:tag"),
+            "localhost:5000"
+        );
+        assert_eq!(registry_hostname("ghcr.io/owner/image"), "ghcr.io");
+
+        assert_eq!(
+            normalize_server_address("https://index.docker.io/v1/"),
+            "docker.io"
+        );
+        assert_eq!(
+            normalize_server_address("http://localhost:5000"),
+            "localhost:5000"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_config_file_auths() {
+        let config: DockerConfigFile = serde_json::from_str(
+            r#"{
+                "auths": {
+                    "https://index.docker.io/v1/": {"auth": "dXNlcjpwYXNzd29yZA=="},
+                    "localhost:5000": {"identitytoken": "token"}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let credentials = config
+            .credentials_for_image("ubuntu")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(credentials.username, Some(String::from("user")));
+        assert_eq!(credentials.password, Some(String::from("password")));
+        assert_eq!(
+            credentials.serveraddress,
+            Some(String::from("https://index.docker.io/v1/"))
+        );
+
+        let credentials = config
+            .credentials_for_image("localhost:5000/image")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(credentials.identitytoken, Some(String::from("token")));
+
+        assert!(config
+            .credentials_for_image("ghcr.io/owner/image")
+            .await
+            .unwrap()
+            .is_none());
+
+        assert_eq!(config.all_credentials().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_docker_config_fallback() {
+        use crate::image::{BuildImageOptions, CreateImageOptions, PushImageOptions};
+        use crate::DockerBuilder;
+
+        use futures_util::stream::TryStreamExt;
+        use hyper::Response;
+        use std::sync::{Arc, Mutex};
+
+        let config: DockerConfigFile = serde_json::from_str(
+            r#"{"auths": {"localhost:5000": {"auth": "dXNlcjpwYXNzd29yZA=="}}}"#,
+        )
+        .unwrap();
+
+        let headers = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&headers);
+        let docker = DockerBuilder::new()
+            .docker_config(config)
+            .build_with_transport(move |request: Request<Body>| {
+                let header = ["X-Registry-Auth", "X-Registry-Config"]
+                    .iter()
+                    .find_map(|name| request.headers().get(*name))
+                    .map(|value| base64::decode_config(value, base64::URL_SAFE).unwrap());
+                recorded.lock().unwrap().push(header.unwrap());
+                async { Ok(Response::new(Body::empty())) }
+            })
+            .unwrap();
+
+        let options = Some(CreateImageOptions {
+            from_image: "localhost:5000/image",
+            ..Default::default()
+        });
+        docker
+            .create_image(options, None, None)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        docker
+            .push_image(
+                "localhost:5000/image",
+                None::<PushImageOptions<String>>,
+                None,
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        let explicit = DockerCredentials {
+            username: Some(String::from("explicit")),
+            ..Default::default()
+        };
+        docker
+            .push_image(
+                "localhost:5000/image",
+                None::<PushImageOptions<String>>,
+                Some(explicit),
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        docker
+            .build_image(BuildImageOptions::<String>::default(), None, None)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let headers: Vec<serde_json::Value> = headers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|header| serde_json::from_slice(header).unwrap())
+            .collect();
+        assert_eq!(headers[0]["username"], "user");
+        assert_eq!(headers[0]["password"], "password");
+        assert_eq!(headers[1]["username"], "user");
+        assert_eq!(headers[2]["username"], "explicit");
+        assert_eq!(headers[2]["password"], serde_json::Value::Null);
+        assert_eq!(headers[3]["localhost:5000"]["username"], "user");
+    }
+
+    #[tokio::test]
+    async fn test_explicit_credentials_only_by_default() {
+        use crate::image::PushImageOptions;
+
+        use futures_util::stream::TryStreamExt;
+        use hyper::Response;
+        use std::sync::{Arc, Mutex};
+
+        let headers = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&headers);
+        let docker = Docker::connect_with_transport(
+            move |request: Request<Body>| {
+                let header = request.headers().get("X-Registry-Auth").unwrap();
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(base64::decode_config(header, base64::URL_SAFE).unwrap());
+                async { Ok(Response::new(Body::empty())) }
+            },
+            "http://localhost",
+            120,
+            crate::API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        docker
+            .push_image(
+                "localhost:5000/image",
+                None::<PushImageOptions<String>>,
+                None,
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let header: serde_json::Value =
+            serde_json::from_slice(&headers.lock().unwrap()[0]).unwrap();
+        assert_eq!(header["username"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,823 @@
+//! Cassette API: record the requests sent to the Docker daemon and its responses into a JSON
+//! cassette, and replay them later without a daemon.
+//!
+//! Recording wraps another [DockerTransport], and replaying serves the recorded responses, body
+//! chunk by body chunk, so that streamed responses such as logs or image pulls are decoded the
+//! same way as from a live daemon. Connections upgraded to another protocol, such as attached
+//! containers, are not recorded.
+//!
+//! Request bodies larger than 64 KiB, such as tar archives uploaded to a container, are recorded
+//! as their length and SHA-256 digest rather than in full.
+//!
+//! Credentials are kept out of recordings: registry auth headers are redacted, and so are the
+//! request bodies of calls that carry passwords, secrets or swarm keys, and the swarm join
+//! tokens, swarm unlock key and registry identity tokens in response bodies.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bollard::cassette::{Cassette, RecordingTransport, ReplayTransport};
+//! use bollard::{Docker, API_DEFAULT_VERSION};
+//!
+//! use hyper::client::Client;
+//! use std::path::Path;
+//!
+//! # #[cfg(unix)]
+//! async move {
+//!     // Record against a live daemon.
+//!     let client = Client::builder().build(hyperlocal::UnixConnector);
+//!     let recorder = RecordingTransport::new(client);
+//!     let docker = Docker::connect_with_transport(
+//!         recorder.clone(),
+//!         "unix:///var/run/docker.sock",
+//!         120,
+//!         API_DEFAULT_VERSION,
+//!     )?;
+//!     docker.version().await?;
+//!     recorder.cassette().save(Path::new("tests/cassettes/version.json"))?;
+//!
+//!     // Replay offline.
+//!     let cassette = Cassette::load(Path::new("tests/cassettes/version.json"))?;
+//!     let docker = Docker::connect_with_transport(
+//!         ReplayTransport::new(cassette),
+//!         "unix:///var/run/docker.sock",
+//!         120,
+//!         API_DEFAULT_VERSION,
+//!     )?;
+//!     docker.version().await?;
+//!     # Ok::<_, bollard::errors::Error>(())
+//! };
+//! ```
+
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use http::header::CONTENT_LENGTH;
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use hyper::body::{Bytes, HttpBody};
+use hyper::{Body, Request, Response, StatusCode};
+use sha2::{Digest, Sha256};
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::errors::Error;
+use crate::errors::Error::*;
+use crate::transport::{DockerTransport, TransportFuture, UpgradedIo};
+
+/// Request headers carrying registry credentials, which are never written to a cassette.
+const REDACTED_HEADERS: &[&str] = &["authorization", "x-registry-auth", "x-registry-config"];
+
+/// Paths of requests whose body carries credentials, secret data or swarm keys, which are never
+/// written to a cassette. A `*` matches any single path segment.
+const REDACTED_BODY_PATHS: &[&str] = &[
+    "/auth",
+    "/secrets/create",
+    "/secrets/*/update",
+    "/swarm/init",
+    "/swarm/join",
+    "/swarm/unlock",
+];
+
+/// Paths of responses whose body carries swarm keys or identity tokens, with the JSON pointers
+/// of the fields that are never written to a cassette.
+const REDACTED_RESPONSE_FIELDS: &[(&str, &[&str])] = &[
+    ("/auth", &["/IdentityToken"]),
+    ("/swarm", &["/JoinTokens/Worker", "/JoinTokens/Manager"]),
+    ("/swarm/unlockkey", &["/UnlockKey"]),
+];
+
+/// Request bodies up to this length are recorded whole, longer ones as their length and digest.
+const MAX_RECORDED_BODY_LENGTH: u64 = 64 * 1024;
+
+/// Placeholder recorded in place of a redacted header value or request body.
+const REDACTED: &str = "<redacted>";
+
+/// A chunk of a recorded body, kept readable when it is valid UTF-8.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyChunk {
+    /// A chunk of UTF-8 text.
+    Text(String),
+    /// A chunk of binary data, base64 encoded.
+    Base64(String),
+}
+
+impl BodyChunk {
+    fn from_bytes(bytes: &[u8]) -> BodyChunk {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => BodyChunk::Text(text.to_string()),
+            Err(_) => BodyChunk::Base64(base64::encode(bytes)),
+        }
+    }
+
+    fn to_bytes(&self) -> Result<Bytes, Error> {
+        match self {
+            BodyChunk::Text(text) => Ok(Bytes::from(text.clone())),
+            BodyChunk::Base64(data) => Ok(Bytes::from(base64::decode(data)?)),
+        }
+    }
+}
+
+/// A request sent to the daemon.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// The HTTP method.
+    pub method: String,
+    /// The request path.
+    pub path: String,
+    /// The query string, if any.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub query: Option<String>,
+    /// The request headers, with credentials redacted.
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// The request body, redacted for requests that carry credentials, and left out when it is
+    /// too long to record.
+    #[serde(default)]
+    pub body: Vec<BodyChunk>,
+    /// The length and digest of a request body too long to record.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body_digest: Option<BodyDigest>,
+}
+
+/// The length and digest of a request body too long to record.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BodyDigest {
+    /// The body length, in bytes.
+    pub length: u64,
+    /// The hex encoded SHA-256 digest of the body.
+    pub sha256: String,
+}
+
+/// A response returned by the daemon.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The response headers.
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// The response body, in the chunks it was received in.
+    #[serde(default)]
+    pub body: Vec<BodyChunk>,
+}
+
+/// A request and the response the daemon returned for it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The request sent to the daemon.
+    pub request: RecordedRequest,
+    /// The response returned by the daemon.
+    pub response: RecordedResponse,
+}
+
+/// A sequence of recorded interactions, stored as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    /// The interactions, in the order the requests were sent.
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Read a cassette from a JSON file.
+    pub fn load(path: &Path) -> Result<Cassette, Error> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Write the cassette to a JSON file.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+fn record_headers(headers: &HeaderMap, redact: bool) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if redact && REDACTED_HEADERS.contains(&name.as_str()) {
+                String::from(REDACTED)
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+/// A [DockerTransport] that passes requests to another transport, and records every request and
+/// response. Clones share the same recording, so keep a clone to save the cassette.
+///
+/// The bodies of requests to `/auth`, `/secrets/create`, `/secrets/{id}/update`, `/swarm/init`,
+/// `/swarm/join` and `/swarm/unlock` are recorded as `<redacted>`, and so are the join tokens
+/// returned by `/swarm`, the key returned by `/swarm/unlockkey` and the identity token returned
+/// by `/auth`. Redact further request paths with [`RecordingTransport::redact_request_body`].
+pub struct RecordingTransport<T> {
+    inner: Arc<T>,
+    interactions: Arc<Mutex<Vec<Interaction>>>,
+    redacted_body_paths: Arc<Vec<String>>,
+}
+
+impl<T> Clone for RecordingTransport<T> {
+    fn clone(&self) -> RecordingTransport<T> {
+        RecordingTransport {
+            inner: self.inner.clone(),
+            interactions: self.interactions.clone(),
+            redacted_body_paths: self.redacted_body_paths.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for RecordingTransport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordingTransport")
+            .field("interactions", &self.interactions)
+            .field("redacted_body_paths", &self.redacted_body_paths)
+            .finish()
+    }
+}
+
+/// Whether the path ends with the segments of the pattern, where `*` matches any segment.
+fn path_matches(path: &str, pattern: &str) -> bool {
+    let path: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    let pattern: Vec<&str> = pattern.trim_start_matches('/').split('/').collect();
+
+    path.len() >= pattern.len()
+        && path[path.len() - pattern.len()..]
+            .iter()
+            .zip(pattern)
+            .all(|(segment, expected)| expected == "*" || *segment == expected)
+}
+
+/// Record a response body with the given JSON fields redacted, or entirely redacted if it is not
+/// JSON.
+fn record_redacted_response(body: &Bytes, fields: &[&str]) -> Vec<BodyChunk> {
+    let mut value: serde_json::Value = match serde_json::from_slice(body) {
+        Ok(value) => value,
+        Err(_) => return record_body(body, true),
+    };
+    for field in fields {
+        if let Some(field) = value.pointer_mut(field).filter(|field| !field.is_null()) {
+            *field = serde_json::Value::from(REDACTED);
+        }
+    }
+    vec![BodyChunk::Text(value.to_string())]
+}
+
+/// A request body recorded as it is sent, kept whole until it grows longer than
+/// [MAX_RECORDED_BODY_LENGTH].
+#[derive(Default)]
+struct BodyRecording {
+    data: Vec<u8>,
+    length: u64,
+    hasher: Sha256,
+}
+
+impl BodyRecording {
+    fn push(&mut self, chunk: &[u8]) {
+        self.length += chunk.len() as u64;
+        self.hasher.update(chunk);
+        if self.length <= MAX_RECORDED_BODY_LENGTH {
+            self.data.extend_from_slice(chunk);
+        } else {
+            self.data = Vec::new();
+        }
+    }
+
+    fn finish(self, redact: bool) -> (Vec<BodyChunk>, Option<BodyDigest>) {
+        if redact || self.length <= MAX_RECORDED_BODY_LENGTH {
+            (record_body(&Bytes::from(self.data), redact), None)
+        } else {
+            let digest = BodyDigest {
+                length: self.length,
+                sha256: hex::encode(self.hasher.finalize()),
+            };
+            (vec![], Some(digest))
+        }
+    }
+}
+
+fn record_body(body: &Bytes, redact: bool) -> Vec<BodyChunk> {
+    if body.is_empty() {
+        vec![]
+    } else if redact {
+        vec![BodyChunk::Text(String::from(REDACTED))]
+    } else {
+        vec![BodyChunk::from_bytes(body)]
+    }
+}
+
+impl<T> RecordingTransport<T>
+where
+    T: DockerTransport + 'static,
+{
+    /// Record the requests sent through the given transport.
+    pub fn new(inner: T) -> RecordingTransport<T> {
+        RecordingTransport {
+            inner: Arc::new(inner),
+            interactions: Arc::new(Mutex::new(Vec::new())),
+            redacted_body_paths: Arc::new(
+                REDACTED_BODY_PATHS
+                    .iter()
+                    .map(|path| path.to_string())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Also redact the body of requests whose path ends with the given path, such as
+    /// `/configs/create` or `/configs/*/update`, where `*` matches any path segment, on top of
+    /// the paths redacted by default.
+    pub fn redact_request_body(mut self, path: &str) -> RecordingTransport<T> {
+        Arc::make_mut(&mut self.redacted_body_paths).push(path.to_string());
+        self
+    }
+
+    /// The interactions recorded so far. Response bodies that are still streaming are recorded
+    /// up to the chunks received so far.
+    pub fn cassette(&self) -> Cassette {
+        // A panic while recording leaves the interactions intact, as each is pushed whole.
+        let interactions = self
+            .interactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        Cassette {
+            interactions: interactions.clone(),
+        }
+    }
+}
+
+impl<T> DockerTransport for RecordingTransport<T>
+where
+    T: DockerTransport + 'static,
+{
+    fn request(&self, request: Request<Body>) -> TransportFuture<Response<Body>> {
+        let inner = self.inner.clone();
+        let interactions = self.interactions.clone();
+        let redact_body = self
+            .redacted_body_paths
+            .iter()
+            .any(|path| path_matches(request.uri().path(), path));
+        let redacted_fields = REDACTED_RESPONSE_FIELDS
+            .iter()
+            .find(|(path, _)| path_matches(request.uri().path(), path))
+            .map(|(_, fields)| *fields);
+
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+            let mut recorded_request = RecordedRequest {
+                method: parts.method.to_string(),
+                path: parts.uri.path().to_string(),
+                query: parts.uri.query().map(String::from),
+                headers: record_headers(&parts.headers, true),
+                ..Default::default()
+            };
+
+            let response = match body.size_hint().exact() {
+                Some(length) if length <= MAX_RECORDED_BODY_LENGTH => {
+                    let body = hyper::body::to_bytes(body).await?;
+                    recorded_request.body = record_body(&body, redact_body);
+                    inner
+                        .request(Request::from_parts(parts, Body::from(body)))
+                        .await?
+                }
+                length => {
+                    // Record long bodies, such as tar uploads, as they are sent, rather than
+                    // holding them in memory.
+                    if let Some(length) = length {
+                        parts.headers.entry(CONTENT_LENGTH).or_insert(length.into());
+                    }
+                    let recording = Arc::new(Mutex::new(BodyRecording::default()));
+                    let body = {
+                        let recording = recording.clone();
+                        body.inspect_ok(move |chunk| {
+                            recording
+                                .lock()
+                                .unwrap_or_else(PoisonError::into_inner)
+                                .push(chunk)
+                        })
+                    };
+                    let response = inner
+                        .request(Request::from_parts(parts, Body::wrap_stream(body)))
+                        .await?;
+
+                    let recording = std::mem::take(
+                        &mut *recording.lock().unwrap_or_else(PoisonError::into_inner),
+                    );
+                    let (body, body_digest) = recording.finish(redact_body);
+                    recorded_request.body = body;
+                    recorded_request.body_digest = body_digest;
+                    response
+                }
+            };
+
+            let (parts, body) = response.into_parts();
+
+            if let Some(fields) = redacted_fields {
+                // Keep the secret fields of the small responses carrying them out of the
+                // recording, while still passing them on to the caller.
+                let body = hyper::body::to_bytes(body).await?;
+                interactions
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(Interaction {
+                        request: recorded_request,
+                        response: RecordedResponse {
+                            status: parts.status.as_u16(),
+                            headers: record_headers(&parts.headers, false)
+                                .into_iter()
+                                .filter(|(name, _)| name != "content-length")
+                                .collect(),
+                            body: record_redacted_response(&body, fields),
+                        },
+                    });
+                return Ok(Response::from_parts(parts, Body::from(body)));
+            }
+
+            let index = {
+                let mut interactions = interactions.lock().unwrap_or_else(PoisonError::into_inner);
+                interactions.push(Interaction {
+                    request: recorded_request,
+                    response: RecordedResponse {
+                        status: parts.status.as_u16(),
+                        headers: record_headers(&parts.headers, false),
+                        body: vec![],
+                    },
+                });
+                interactions.len() - 1
+            };
+
+            let body = body.inspect_ok(move |chunk| {
+                interactions.lock().unwrap_or_else(PoisonError::into_inner)[index]
+                    .response
+                    .body
+                    .push(BodyChunk::from_bytes(chunk));
+            });
+
+            Ok(Response::from_parts(parts, Body::wrap_stream(body)))
+        })
+    }
+
+    fn upgrade(&self, response: Response<Body>) -> TransportFuture<Box<dyn UpgradedIo>> {
+        self.inner.upgrade(response)
+    }
+}
+
+/// A [DockerTransport] serving the responses of a [Cassette]. Each request is answered by the
+/// first interaction not yet replayed with the same method, path and query.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    interactions: Mutex<Vec<Option<Interaction>>>,
+}
+
+impl ReplayTransport {
+    /// Replay the given cassette.
+    pub fn new(cassette: Cassette) -> ReplayTransport {
+        ReplayTransport {
+            interactions: Mutex::new(cassette.interactions.into_iter().map(Some).collect()),
+        }
+    }
+
+    fn take(&self, request: &Request<Body>) -> Option<Interaction> {
+        let path = request.uri().path();
+        let query = request.uri().query();
+
+        self.interactions
+            .lock()
+            .ok()?
+            .iter_mut()
+            .find(|interaction| match interaction {
+                Some(Interaction { request: r, .. }) => {
+                    r.method == request.method().as_str()
+                        && r.path == path
+                        && r.query.as_deref() == query
+                }
+                None => false,
+            })
+            .and_then(Option::take)
+    }
+}
+
+fn replay_response(recorded: RecordedResponse) -> Result<Response<Body>, Error> {
+    let chunks = recorded
+        .body
+        .iter()
+        .map(BodyChunk::to_bytes)
+        .collect::<Result<Vec<Bytes>, Error>>()?;
+
+    let mut response = Response::new(Body::wrap_stream(
+        stream::iter(chunks).map(Ok::<_, std::io::Error>),
+    ));
+    *response.status_mut() = StatusCode::from_u16(recorded.status).map_err(http::Error::from)?;
+    for (name, value) in recorded.headers {
+        response.headers_mut().append(
+            HeaderName::from_bytes(name.as_bytes()).map_err(http::Error::from)?,
+            HeaderValue::from_str(&value).map_err(http::Error::from)?,
+        );
+    }
+
+    Ok(response)
+}
+
+impl DockerTransport for ReplayTransport {
+    fn request(&self, request: Request<Body>) -> TransportFuture<Response<Body>> {
+        let interaction = self.take(&request);
+        let method = request.method().to_string();
+        let uri = request.uri().to_string();
+
+        Box::pin(async move {
+            match interaction {
+                Some(interaction) => replay_response(interaction.response),
+                None => Err(CassetteInteractionNotFoundError { method, uri }),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::DockerCredentials;
+    use crate::config::UpdateConfigOptions;
+    use crate::image::CreateImageOptions;
+    use crate::secret::UpdateSecretOptions;
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    async fn fake_daemon(request: Request<Body>) -> Result<Response<Body>, Error> {
+        match request.uri().path() {
+            path if path.ends_with("/images/create") => {
+                let chunks: Vec<Result<&'static str, std::io::Error>> = vec![
+                    Ok("{\"status\":\"Pulling from library/hello-world\"}\n{\"status\":"),
+                    Ok("\"Pull complete\"}\n"),
+                ];
+                Ok(Response::new(Body::wrap_stream(stream::iter(chunks))))
+            }
+            path if path.ends_with("/version") => {
+                Ok(Response::new(Body::from(r#"{"ApiVersion":"1.41"}"#)))
+            }
+            _ => Ok(Response::builder()
+                .status(404)
+                .body(Body::from(r#"{"message":"page not found"}"#))?),
+        }
+    }
+
+    async fn exercise(docker: &Docker) -> Result<Vec<String>, Error> {
+        let mut statuses: Vec<String> = docker
+            .create_image(
+                Some(CreateImageOptions {
+                    from_image: "hello-world",
+                    ..Default::default()
+                }),
+                None,
+                None,
+            )
+            .map_ok(|info| info.status.unwrap_or_default())
+            .try_collect()
+            .await?;
+
+        statuses.push(docker.version().await?.api_version.unwrap_or_default());
+
+        if let Err(e) = docker.inspect_container("missing", None).await {
+            statuses.push(e.to_string());
+        }
+
+        Ok(statuses)
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let recorder = RecordingTransport::new(fake_daemon);
+        let docker = Docker::connect_with_transport(
+            recorder.clone(),
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+        let recorded = exercise(&docker).await.unwrap();
+
+        let path = std::env::temp_dir().join(format!("bollard-cassette-{}", std::process::id()));
+        recorder.cassette().save(&path).unwrap();
+        let cassette = Cassette::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(cassette, recorder.cassette());
+        assert_eq!(cassette.interactions.len(), 3);
+        assert_eq!(cassette.interactions[0].response.body.len(), 2);
+        assert_eq!(
+            cassette.interactions[0].request.query.as_deref(),
+            Some("fromImage=hello-world&fromSrc=&repo=&tag=&platform=")
+        );
+
+        let docker = Docker::connect_with_transport(
+            ReplayTransport::new(cassette),
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+        assert_eq!(exercise(&docker).await.unwrap(), recorded);
+
+        assert!(matches!(
+            docker.version().await,
+            Err(CassetteInteractionNotFoundError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_redacted_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-registry-auth", HeaderValue::from_static("secret"));
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+        let recorded = record_headers(&headers, true);
+        assert!(recorded.contains(&(String::from("x-registry-auth"), String::from(REDACTED))));
+        assert!(recorded.contains(&(
+            String::from("content-type"),
+            String::from("application/json")
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_redacted_request_bodies() {
+        let recorder = RecordingTransport::new(|_request: Request<Body>| async move {
+            Ok(Response::new(Body::from(r#"{"Id":"id"}"#)))
+        })
+        .redact_request_body("/configs/create");
+        let docker = Docker::connect_with_transport(
+            recorder.clone(),
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        docker
+            .create_secret(Default::default(), Some("password".as_bytes()))
+            .await
+            .unwrap();
+        docker
+            .create_config(Default::default(), Some("worker_processes 1;".as_bytes()))
+            .await
+            .unwrap();
+        docker
+            .update_secret("id", Default::default(), UpdateSecretOptions { version: 1 })
+            .await
+            .unwrap();
+        docker
+            .update_config("id", Default::default(), UpdateConfigOptions { version: 1 })
+            .await
+            .unwrap();
+
+        let cassette = recorder.cassette();
+        let redacted = vec![BodyChunk::Text(String::from(REDACTED))];
+        assert_eq!(cassette.interactions[0].request.body, redacted);
+        assert_eq!(cassette.interactions[1].request.body, redacted);
+        assert_eq!(cassette.interactions[2].request.body, redacted);
+        assert_ne!(cassette.interactions[3].request.body, redacted);
+    }
+
+    #[tokio::test]
+    async fn test_long_request_bodies() {
+        let recorder = RecordingTransport::new(|request: Request<Body>| async move {
+            hyper::body::to_bytes(request.into_body()).await?;
+            Ok(Response::new(Body::empty()))
+        });
+        let docker = Docker::connect_with_transport(
+            recorder.clone(),
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        let tar = vec![0; 100_000];
+        let chunks: Vec<Result<Vec<u8>, std::io::Error>> =
+            vec![Ok(tar[..50_000].to_vec()), Ok(tar[50_000..].to_vec())];
+        docker
+            .upload_to_container::<String>("my-container", None, Body::from(tar.clone()))
+            .await
+            .unwrap();
+        docker
+            .upload_to_container::<String>(
+                "my-container",
+                None,
+                Body::wrap_stream(stream::iter(chunks)),
+            )
+            .await
+            .unwrap();
+        docker
+            .upload_to_container::<String>("my-container", None, Body::from(vec![0; 1000]))
+            .await
+            .unwrap();
+
+        let cassette = recorder.cassette();
+        let digest = Some(BodyDigest {
+            length: 100_000,
+            sha256: hex::encode(Sha256::digest(&tar)),
+        });
+        assert!(cassette.interactions[0].request.body.is_empty());
+        assert_eq!(cassette.interactions[0].request.body_digest, digest);
+        assert!(cassette.interactions[1].request.body.is_empty());
+        assert_eq!(cassette.interactions[1].request.body_digest, digest);
+        assert_eq!(cassette.interactions[2].request.body.len(), 1);
+        assert_eq!(cassette.interactions[2].request.body_digest, None);
+    }
+
+    #[tokio::test]
+    async fn test_redacted_response_bodies() {
+        let recorder = RecordingTransport::new(|request: Request<Body>| async move {
+            let body = match request.uri().path() {
+                path if path.ends_with("/swarm/unlockkey") => r#"{"UnlockKey":"SWMKEY-1-key"}"#,
+                path if path.ends_with("/swarm") => {
+                    r#"{"ID":"swarm","JoinTokens":{"Worker":"SWMTKN-1-worker","Manager":"SWMTKN-1-manager"}}"#
+                }
+                _ => r#"{"Status":"Login Succeeded","IdentityToken":"token"}"#,
+            };
+            Ok(Response::new(Body::from(body)))
+        });
+        let docker = Docker::connect_with_transport(
+            recorder.clone(),
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        let swarm = docker.inspect_swarm().await.unwrap();
+        let unlock_key = docker.get_unlock_key().await.unwrap();
+        let auth = docker
+            .check_auth(DockerCredentials {
+                username: Some(String::from("user")),
+                password: Some(String::from("password")),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            swarm.join_tokens.unwrap().worker,
+            Some(String::from("SWMTKN-1-worker"))
+        );
+        assert_eq!(unlock_key.unlock_key, Some(String::from("SWMKEY-1-key")));
+        assert_eq!(auth.identity_token, Some(String::from("token")));
+
+        let recorded: String = recorder
+            .cassette()
+            .interactions
+            .iter()
+            .flat_map(|interaction| interaction.response.body.clone())
+            .map(|chunk| match chunk {
+                BodyChunk::Text(text) => text,
+                BodyChunk::Base64(data) => data,
+            })
+            .collect();
+        for secret in &[
+            "SWMTKN-1-worker",
+            "SWMTKN-1-manager",
+            "SWMKEY-1-key",
+            "\"token\"",
+        ] {
+            assert!(!recorded.contains(secret), "{} was recorded", secret);
+        }
+
+        let docker = Docker::connect_with_transport(
+            ReplayTransport::new(recorder.cassette()),
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+        let swarm = docker.inspect_swarm().await.unwrap();
+        assert_eq!(swarm.id, Some(String::from("swarm")));
+        assert_eq!(
+            swarm.join_tokens.unwrap().manager,
+            Some(String::from(REDACTED))
+        );
+    }
+
+    #[cfg(all(unix, feature = "test-server"))]
+    #[tokio::test]
+    async fn test_record_and_replay_unix_socket() {
+        let server = crate::test_server::TestServer::start().unwrap();
+        let addr = format!("unix://{}", server.socket_path().display());
+        let recorder =
+            RecordingTransport::new(hyper::Client::builder().build(hyperlocal::UnixConnector));
+        let docker =
+            Docker::connect_with_transport(recorder.clone(), &addr, 120, API_DEFAULT_VERSION)
+                .unwrap();
+        let recorded = exercise(&docker).await.unwrap();
+        drop(server);
+
+        assert_eq!(recorder.cassette().interactions.len(), 3);
+
+        let docker = Docker::connect_with_transport(
+            ReplayTransport::new(recorder.cassette()),
+            &addr,
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+        assert_eq!(exercise(&docker).await.unwrap(), recorded);
+    }
+}
@@ -0,0 +1,354 @@
+//! Config API: Configs are application configurations that can be used by services. Swarm mode
+//! must be enabled for these endpoints to work.
+
+pub use crate::models::*;
+
+use super::Docker;
+use crate::docker::encode_spec_data;
+use crate::errors::Error;
+use http::request::Builder;
+use hyper::{body::Body as HyperBody, Method};
+use serde::ser::Serialize;
+
+use std::{collections::HashMap, hash::Hash, path::Path};
+
+/// Parameters used in the [List Configs API](super::Docker::list_configs())
+///
+/// ## Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// use bollard::config::ListConfigsOptions;
+///
+/// let mut filters = HashMap::new();
+/// filters.insert("label", vec!["config-label=label-value"]);
+///
+/// ListConfigsOptions{
+///     filters,
+/// };
+/// ```
+///
+/// ```rust
+/// # use bollard::config::ListConfigsOptions;
+/// # use std::default::Default;
+///
+/// let options: ListConfigsOptions<&str> = Default::default();
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListConfigsOptions<T>
+where
+    T: Into<String> + Eq + Hash + Serialize,
+{
+    /// Filters to process on the configs list, encoded as JSON. Available filters:
+    ///  - `id`=`<config id>` a config's ID
+    ///  - `label`=`key` or `label`=`"key=value"` of a config label
+    ///  - `name`=`<config name>` a config's name
+    ///  - `names`=`<config name>` a config's name, matched exactly
+    #[serde(serialize_with = "crate::docker::serialize_as_json")]
+    pub filters: HashMap<T, Vec<T>>,
+}
+
+/// Parameters used in the [Update Config API](Docker::update_config())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::config::UpdateConfigOptions;
+///
+/// UpdateConfigOptions{
+///     version: 1234,
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, Serialize)]
+pub struct UpdateConfigOptions {
+    /// The version number of the config object being updated. This is required to avoid
+    /// conflicting writes.
+    pub version: u64,
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # List Configs
+    ///
+    /// Returns a list of configs.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [ListConfigsOptions](ListConfigsOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Configs](Config), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::config::ListConfigsOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("label", vec!["config-label=label-value"]);
+    ///
+    /// let options = Some(ListConfigsOptions{
+    ///     filters,
+    /// });
+    ///
+    /// docker.list_configs(options);
+    /// ```
+    pub async fn list_configs<T>(
+        &self,
+        options: Option<ListConfigsOptions<T>>,
+    ) -> Result<Vec<Config>, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let url = "/configs";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Create Config
+    ///
+    /// Create a new config on the docker swarm.
+    ///
+    /// # Arguments
+    ///
+    ///  - [ConfigSpec](ConfigSpec) struct.
+    ///  - Optional raw config data. If set, it is base64 encoded into the `data` field of the
+    ///    spec, replacing any value already present.
+    ///
+    /// # Returns
+    ///
+    ///  - A [IdResponse](IdResponse) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::config::ConfigSpec;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let config_spec = ConfigSpec {
+    ///     name: Some(String::from("config-name")),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.create_config(config_spec, Some("worker_processes 1;".as_bytes()));
+    /// ```
+    pub async fn create_config(
+        &self,
+        mut config_spec: ConfigSpec,
+        data: Option<&[u8]>,
+    ) -> Result<IdResponse, Error> {
+        let url = "/configs/create";
+
+        if let Some(data) = data {
+            config_spec.data = Some(encode_spec_data(data));
+        }
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            None::<String>,
+            Docker::serialize_payload(Some(config_spec)),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Create Config From File
+    ///
+    /// Create a new config on the docker swarm, using the contents of a local file as the config
+    /// data. If the spec does not set a name, the file name is used.
+    ///
+    /// # Arguments
+    ///
+    ///  - [ConfigSpec](ConfigSpec) struct.
+    ///  - Path to the local file.
+    ///
+    /// # Returns
+    ///
+    ///  - A [IdResponse](IdResponse) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::config::ConfigSpec;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let config_spec = ConfigSpec {
+    ///     name: Some(String::from("nginx.conf")),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.create_config_from_file(config_spec, "/etc/nginx/nginx.conf");
+    /// ```
+    pub async fn create_config_from_file<P>(
+        &self,
+        mut config_spec: ConfigSpec,
+        path: P,
+    ) -> Result<IdResponse, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        let data = tokio::fs::read(path).await?;
+
+        if config_spec.name.is_none() {
+            config_spec.name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned());
+        }
+
+        self.create_config(config_spec, Some(&data)).await
+    }
+
+    /// ---
+    ///
+    /// # Inspect Config
+    ///
+    /// Inspect a config.
+    ///
+    /// # Arguments
+    ///
+    ///  - Config id or name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - [Config](Config), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.inspect_config("config-id");
+    /// docker.inspect_config("config-name");
+    /// ```
+    pub async fn inspect_config(&self, config_id: &str) -> Result<Config, Error> {
+        let url = format!("/configs/{}", config_id);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Update Config
+    ///
+    /// Update an existing config. Currently, only the `labels` field can be updated.
+    ///
+    /// # Arguments
+    ///
+    ///  - Config id or name as a string slice.
+    ///  - [ConfigSpec](ConfigSpec) struct.
+    ///  - [UpdateConfigOptions](UpdateConfigOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::config::UpdateConfigOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let result = async move {
+    ///     let existing = docker.inspect_config("my-config").await?;
+    ///     let version = existing.version.unwrap().index.unwrap();
+    ///     let mut spec = existing.spec.unwrap().clone();
+    ///
+    ///     let mut labels = HashMap::new();
+    ///     labels.insert(String::from("config-label"), String::from("label-value"));
+    ///     spec.labels = Some(labels.clone());
+    ///
+    ///     let options = UpdateConfigOptions { version };
+    ///
+    ///     docker.update_config("my-config", spec, options).await
+    /// };
+    /// ```
+    pub async fn update_config(
+        &self,
+        config_id: &str,
+        config_spec: ConfigSpec,
+        options: UpdateConfigOptions,
+    ) -> Result<(), Error> {
+        let url = format!("/configs/{}/update", config_id);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::POST),
+            Some(options),
+            Docker::serialize_payload(Some(config_spec)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Delete Config
+    ///
+    /// Delete a config, fails when more than one service use that config.
+    ///
+    /// # Arguments
+    ///
+    ///  - Config id or name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.delete_config("config-name");
+    /// ```
+    pub async fn delete_config(&self, config_id: &str) -> Result<(), Error> {
+        let url = format!("/configs/{}", config_id);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::DELETE),
+            None::<String>,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_unit(req).await
+    }
+}
@@ -2,12 +2,14 @@
 
 use chrono::{DateTime, Utc};
 use futures_core::Stream;
-use http::header::{CONNECTION, CONTENT_TYPE, UPGRADE};
+use futures_util::stream::StreamExt;
+use http::header::{CONNECTION, CONTENT_TYPE, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION, UPGRADE};
 use http::request::Builder;
 use hyper::{body::Bytes, Body, Method};
 use serde::Serialize;
 use tokio::io::AsyncWrite;
 use tokio_util::codec::FramedRead;
+use tokio_util::io::StreamReader;
 
 use std::cmp::Eq;
 use std::collections::HashMap;
@@ -20,6 +22,7 @@ use crate::errors::Error;
 
 use crate::models::*;
 use crate::read::NewlineLogOutputDecoder;
+use crate::websocket::{websocket_key, websocket_reader, WebSocketWriter};
 
 /// Parameters used in the [List Container API](Docker::list_containers())
 ///
@@ -375,7 +378,7 @@ pub struct AttachContainerResults {
 }
 
 impl fmt::Debug for AttachContainerResults {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "AttachContainerResults")
     }
 }
@@ -474,7 +477,7 @@ where
     pub ps_args: T,
 }
 
-fn is_zero(val: &i64) -> bool {
+pub(crate) fn is_zero(val: &i64) -> bool {
     val == &0i64
 }
 
@@ -1088,6 +1091,84 @@ where
     pub path: T,
 }
 
+/// Parameters used in the [Stat Container Path API](Docker::stat_container_path())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::StatContainerPathOptions;
+///
+/// StatContainerPathOptions{
+///     path: "/etc/hosts",
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StatContainerPathOptions<T>
+where
+    T: Into<String> + Serialize,
+{
+    /// Resource in the container’s filesystem to stat.
+    pub path: T,
+}
+
+/// Result type for the [Stat Container Path API](Docker::stat_container_path())
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerPathStat {
+    /// Base name of the file.
+    pub name: String,
+    /// Size of the file in bytes.
+    pub size: i64,
+    /// File mode and permission bits, encoded as a go `os.FileMode`.
+    pub mode: u32,
+    /// Modification time of the file.
+    pub mtime: DateTime<Utc>,
+    /// Target of the symbolic link, if the path is a link.
+    pub link_target: String,
+}
+
+impl ContainerPathStat {
+    const MODE_DIR: u32 = 1 << 31;
+    const MODE_SYMLINK: u32 = 1 << 27;
+
+    /// Whether the path is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.mode & ContainerPathStat::MODE_DIR != 0
+    }
+
+    /// Whether the path is a symbolic link.
+    pub fn is_symlink(&self) -> bool {
+        self.mode & ContainerPathStat::MODE_SYMLINK != 0
+    }
+
+    /// Unix permission bits of the path.
+    pub fn permissions(&self) -> u32 {
+        self.mode & 0o777
+    }
+}
+
+/// Resize configuration used in the [Resize Container Tty API](Docker::resize_container_tty())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::ResizeContainerTtyOptions;
+///
+/// ResizeContainerTtyOptions {
+///     width: 50,
+///     height: 10,
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ResizeContainerTtyOptions {
+    /// Height of the TTY session in characters
+    #[serde(rename = "h")]
+    pub height: u16,
+    /// Width of the TTY session in characters
+    #[serde(rename = "w")]
+    pub width: u16,
+}
+
 impl Docker {
     /// ---
     ///
@@ -1124,6 +1205,7 @@ impl Docker {
     ///
     /// docker.list_containers(options);
     /// ```
+    #[allow(clippy::extra_unused_lifetimes)]
     pub async fn list_containers<'de, T>(
         &self,
         options: Option<ListContainersOptions<T>>,
@@ -1353,7 +1435,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [ContainerWaitResponse](ContainerWaitResponse), wrapped in a
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -1386,7 +1468,7 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_stream(req)
+        self.for_silent_stream().process_into_stream(req)
     }
 
     /// ---
@@ -1453,6 +1535,75 @@ impl Docker {
         })
     }
 
+    /// ---
+    ///
+    /// # Attach Container Websocket
+    ///
+    /// Attach to a container over a websocket, rather than an upgraded HTTP connection. This is
+    /// useful when the daemon sits behind a proxy that does not pass through connection upgrades.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as a string slice.
+    ///  - Optional [Attach Container Options](AttachContainerOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [AttachContainerResults](AttachContainerResults) wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::AttachContainerOptions;
+    ///
+    /// let options = Some(AttachContainerOptions::<String>{
+    ///     stdin: Some(true),
+    ///     stdout: Some(true),
+    ///     stderr: Some(true),
+    ///     stream: Some(true),
+    ///     logs: Some(true),
+    ///     detach_keys: Some("ctrl-c".to_string()),
+    /// });
+    ///
+    /// docker.attach_container_websocket("hello-world", options);
+    /// ```
+    pub async fn attach_container_websocket<T>(
+        &self,
+        container_name: &str,
+        options: Option<AttachContainerOptions<T>>,
+    ) -> Result<AttachContainerResults, Error>
+    where
+        T: Into<String> + Serialize + Default,
+    {
+        let url = format!("/containers/{}/attach/ws", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new()
+                .method(Method::GET)
+                .header(CONNECTION, "Upgrade")
+                .header(UPGRADE, "websocket")
+                .header(SEC_WEBSOCKET_VERSION, "13")
+                .header(SEC_WEBSOCKET_KEY, websocket_key()),
+            options,
+            Ok(Body::empty()),
+        );
+
+        let (write, read) = self.process_into_websocket(req).await?.split();
+        let log = FramedRead::new(
+            StreamReader::new(websocket_reader(read)),
+            NewlineLogOutputDecoder::new(),
+        );
+
+        Ok(AttachContainerResults {
+            output: Box::pin(log),
+            input: Box::pin(WebSocketWriter::new(write)),
+        })
+    }
+
     /// ---
     ///
     /// # Restart Container
@@ -1606,7 +1757,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [Log Output](LogOutput) enum, wrapped in a
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -1634,6 +1785,8 @@ impl Docker {
         T: Into<String> + Serialize,
     {
         let url = format!("/containers/{}/logs", container_name);
+        #[allow(clippy::unnecessary_map_or)]
+        let follow = options.as_ref().map_or(false, |options| options.follow);
 
         let req = self.build_request(
             &url,
@@ -1642,7 +1795,11 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_stream_string(req)
+        if follow {
+            self.for_silent_stream().process_into_stream_string(req)
+        } else {
+            self.process_into_stream_string(req)
+        }
     }
 
     /// ---
@@ -1658,7 +1815,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - An Option of Vector of [Container Change Response Item](ContainerChangeResponseItem) structs, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -1698,7 +1855,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [Stats](Stats) struct, wrapped in a
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -2109,4 +2266,150 @@ impl Docker {
 
         self.process_into_body(req)
     }
+
+    /// ---
+    ///
+    /// # Resize Container Tty
+    ///
+    /// Resize the container's TTY. This endpoint only works if `tty` was specified as part of
+    /// creating the container.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - [Resize Container Tty Options](ResizeContainerTtyOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::ResizeContainerTtyOptions;
+    ///
+    /// let options = ResizeContainerTtyOptions {
+    ///     width: 50,
+    ///     height: 20,
+    /// };
+    ///
+    /// docker.resize_container_tty("hello-world", options);
+    /// ```
+    pub async fn resize_container_tty(
+        &self,
+        container_name: &str,
+        options: ResizeContainerTtyOptions,
+    ) -> Result<(), Error> {
+        let url = format!("/containers/{}/resize", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::POST),
+            Some(options),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Export Container
+    ///
+    /// Export the contents of a container as a tarball, containing the flattened filesystem of the
+    /// container.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - An uncompressed TAR archive, wrapped in a Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.export_container("hello-world");
+    /// ```
+    pub fn export_container(
+        &self,
+        container_name: &str,
+    ) -> impl Stream<Item = Result<Bytes, Error>> {
+        let url = format!("/containers/{}/export", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(Body::empty()),
+        );
+
+        self.process_into_body(req)
+    }
+
+    /// ---
+    ///
+    /// # Stat Container Path
+    ///
+    /// Get information about a file or directory in a container's filesystem, without
+    /// downloading it.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - [Stat Container Path Options](StatContainerPathOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Container Path Stat](ContainerPathStat), wrapped in a Future. If the path does not
+    ///    exist, a `DockerResponseNotFoundError` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::StatContainerPathOptions;
+    ///
+    /// let options = StatContainerPathOptions{
+    ///     path: "/etc/hosts",
+    /// };
+    ///
+    /// docker.stat_container_path("my-container", options);
+    /// ```
+    pub async fn stat_container_path<T>(
+        &self,
+        container_name: &str,
+        options: StatContainerPathOptions<T>,
+    ) -> Result<ContainerPathStat, Error>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = format!("/containers/{}/archive", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::HEAD),
+            Some(options),
+            Ok(Body::empty()),
+        );
+
+        let headers = self.process_into_headers(req).await?;
+
+        let header = headers.get("X-Docker-Container-Path-Stat").ok_or_else(|| {
+            Error::MissingHeaderError {
+                header: String::from("X-Docker-Container-Path-Stat"),
+            }
+        })?;
+
+        let decoded = base64::decode(header.as_bytes())?;
+
+        Ok(serde_json::from_slice(&decoded)?)
+    }
 }
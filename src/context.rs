@@ -0,0 +1,336 @@
+//! Docker contexts, as selected with `docker context use`, stored below the docker configuration
+//! directory.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::Docker;
+use crate::docker::{DockerBuilder, DEFAULT_LOCAL_HOST};
+use crate::errors::Error;
+use crate::errors::Error::*;
+
+/// The TLS files of a context: the private key, the client certificate and the certificate chain.
+const TLS_FILES: [&str; 3] = ["key.pem", "cert.pem", "ca.pem"];
+
+/// The context that stands for `DOCKER_HOST`, or the local socket, rather than a stored context.
+const DEFAULT_CONTEXT: &str = "default";
+
+#[derive(Debug, Default, Deserialize)]
+struct ConfigFile {
+    #[serde(rename = "currentContext", default)]
+    current_context: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContextMeta {
+    name: String,
+    #[serde(default)]
+    endpoints: HashMap<String, ContextEndpoint>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContextEndpoint {
+    #[serde(default)]
+    host: Option<String>,
+    #[serde(rename = "SkipTLSVerify", default)]
+    skip_tls_verify: bool,
+}
+
+/// The docker endpoint of a stored context.
+#[derive(Debug, PartialEq)]
+struct Context {
+    host: String,
+    tls_dir: Option<PathBuf>,
+    skip_tls_verify: bool,
+}
+
+/// The directory holding `config.json` and the context store, sourced from the `DOCKER_CONFIG`
+/// environment variable and defaulting to `~/.docker`.
+pub(crate) fn docker_config_dir() -> Result<PathBuf, Error> {
+    if let Ok(path) = env::var("DOCKER_CONFIG") {
+        Ok(PathBuf::from(path))
+    } else {
+        let home = dirs_next::home_dir().ok_or(NoHomePathError)?;
+        Ok(home.join(".docker"))
+    }
+}
+
+fn current_context(config_dir: &Path) -> Result<Option<String>, Error> {
+    let path = config_dir.join("config.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let config: ConfigFile = serde_json::from_slice(&fs::read(path)?)?;
+    Ok(config.current_context.filter(|name| !name.is_empty()))
+}
+
+fn read_meta(path: &Path) -> Result<ContextMeta, Error> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+/// Find a context by name. Contexts are stored in directories named after a digest of the context
+/// name, so each `meta.json` is read to match on its `Name` field. A `meta.json` that cannot be
+/// read or parsed is skipped, as it may belong to any other context.
+fn read_context(config_dir: &Path, name: &str) -> Result<Context, Error> {
+    let meta_dir = config_dir.join("contexts").join("meta");
+    let entries = match fs::read_dir(&meta_dir) {
+        Ok(entries) => entries,
+        Err(_) => {
+            return Err(DockerContextNotFoundError {
+                name: name.to_string(),
+            })
+        }
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        let meta_path = entry.path().join("meta.json");
+        if !meta_path.is_file() {
+            continue;
+        }
+
+        let mut meta = match read_meta(&meta_path) {
+            Ok(meta) => meta,
+            Err(e) => {
+                warn!("skipping docker context {}: {}", meta_path.display(), e);
+                continue;
+            }
+        };
+        if meta.name != name {
+            continue;
+        }
+
+        let endpoint = meta.endpoints.remove("docker").unwrap_or_default();
+        let host = endpoint
+            .host
+            .filter(|host| !host.is_empty())
+            .unwrap_or_else(|| DEFAULT_LOCAL_HOST.to_string());
+
+        let tls_dir = config_dir
+            .join("contexts")
+            .join("tls")
+            .join(entry.file_name())
+            .join("docker");
+
+        return Ok(Context {
+            host,
+            tls_dir: if tls_dir.is_dir() {
+                Some(tls_dir)
+            } else {
+                None
+            },
+            skip_tls_verify: endpoint.skip_tls_verify,
+        });
+    }
+
+    Err(DockerContextNotFoundError {
+        name: name.to_string(),
+    })
+}
+
+/// The TLS material stored with a context, if any. Material missing some of the TLS files is
+/// rejected, rather than completed with files meant for another daemon.
+fn tls_files(name: &str, tls_dir: Option<&Path>) -> Result<Option<[PathBuf; 3]>, Error> {
+    let tls_dir = match tls_dir {
+        Some(tls_dir) => tls_dir,
+        None => return Ok(None),
+    };
+    let missing: Vec<&str> = TLS_FILES
+        .iter()
+        .filter(|file| !tls_dir.join(file).is_file())
+        .copied()
+        .collect();
+
+    match missing.len() {
+        0 => Ok(Some([
+            tls_dir.join("key.pem"),
+            tls_dir.join("cert.pem"),
+            tls_dir.join("ca.pem"),
+        ])),
+        3 => Ok(None),
+        _ => Err(DockerContextUnsupportedError {
+            name: name.to_string(),
+            reason: format!("its TLS material is missing {}", missing.join(", ")),
+        }),
+    }
+}
+
+impl Docker {
+    /// Connect to the daemon of a context created with `docker context create`.
+    ///
+    /// # Defaults
+    ///
+    ///  - Contexts are looked up in the directory named by the `DOCKER_CONFIG` environment
+    ///    variable, or `~/.docker`.
+    ///  - The `default` context connects as [`DockerBuilder::build`] does without a url.
+    ///  - Certificates stored with the context are used, which requires the `ssl` feature. A
+    ///    context must store all of `key.pem`, `cert.pem` and `ca.pem`, or none of them.
+    ///  - TLS is only used if the context stores certificates, regardless of the
+    ///    `DOCKER_TLS_VERIFY` environment variable.
+    ///  - Contexts that skip the verification of the daemon certificate are not supported.
+    ///  - The request timeout defaults to 2 minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_context("my-remote-context").unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    ///
+    /// [`DockerBuilder::build`]: crate::DockerBuilder::build()
+    pub fn connect_with_context(name: &str) -> Result<Docker, Error> {
+        if name == DEFAULT_CONTEXT {
+            return DockerBuilder::new().build();
+        }
+
+        let context = read_context(&docker_config_dir()?, name)?;
+        if context.skip_tls_verify {
+            return Err(DockerContextUnsupportedError {
+                name: name.to_string(),
+                reason: String::from("skipping TLS verification is not supported"),
+            });
+        }
+        let tls_files = tls_files(name, context.tls_dir.as_deref())?;
+        #[cfg(not(feature = "ssl"))]
+        if tls_files.is_some() {
+            return Err(SslFeatureDisabledError { url: context.host });
+        }
+
+        let builder = DockerBuilder::new()
+            .url(&context.host)
+            .ignore_tls_verify_env();
+
+        #[cfg(feature = "ssl")]
+        let builder = match tls_files {
+            Some([ref key, ref cert, ref ca]) => builder.ssl_key(key).ssl_cert(cert).ssl_ca(ca),
+            None => builder,
+        };
+
+        builder.build()
+    }
+
+    /// Connect to the same daemon as the docker CLI.
+    ///
+    /// # Defaults
+    ///
+    ///  - The `DOCKER_HOST` environment variable is used if set.
+    ///  - Otherwise, the context named by the `DOCKER_CONTEXT` environment variable is used if
+    ///    set.
+    ///  - Otherwise, the `currentContext` of the docker `config.json` is used if set.
+    ///  - Otherwise, the local unix socket or named pipe is used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_defaults().unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_defaults() -> Result<Docker, Error> {
+        if env::var("DOCKER_HOST").is_ok() {
+            return DockerBuilder::new().build();
+        }
+
+        if let Ok(name) = env::var("DOCKER_CONTEXT") {
+            return Docker::connect_with_context(&name);
+        }
+
+        match current_context(&docker_config_dir()?)? {
+            Some(name) => Docker::connect_with_context(&name),
+            None => DockerBuilder::new().build(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_context() {
+        let config_dir = env::temp_dir().join(format!("bollard-context-{}", std::process::id()));
+        let meta_dir = config_dir.join("contexts").join("meta");
+        let tls_dir = config_dir.join("contexts").join("tls");
+
+        fs::create_dir_all(meta_dir.join("a1")).unwrap();
+        fs::write(
+            meta_dir.join("a1").join("meta.json"),
+            r#"{"Name":"remote","Metadata":{},"Endpoints":{"docker":{"Host":"tcp://10.0.0.1:2376","SkipTLSVerify":false}}}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(tls_dir.join("a1").join("docker")).unwrap();
+
+        fs::create_dir_all(meta_dir.join("b2")).unwrap();
+        fs::write(
+            meta_dir.join("b2").join("meta.json"),
+            r#"{"Name":"local","Metadata":{},"Endpoints":{"docker":{"Host":"","SkipTLSVerify":true}}}"#,
+        )
+        .unwrap();
+
+        fs::create_dir_all(meta_dir.join("c3")).unwrap();
+        fs::write(meta_dir.join("c3").join("meta.json"), "{\"Name\":").unwrap();
+
+        fs::write(
+            config_dir.join("config.json"),
+            r#"{"auths":{},"currentContext":"remote"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            current_context(&config_dir).unwrap(),
+            Some(String::from("remote"))
+        );
+        assert_eq!(
+            read_context(&config_dir, "remote").unwrap(),
+            Context {
+                host: String::from("tcp://10.0.0.1:2376"),
+                tls_dir: Some(tls_dir.join("a1").join("docker")),
+                skip_tls_verify: false,
+            }
+        );
+        assert_eq!(
+            read_context(&config_dir, "local").unwrap(),
+            Context {
+                host: String::from(DEFAULT_LOCAL_HOST),
+                tls_dir: None,
+                skip_tls_verify: true,
+            }
+        );
+        assert!(matches!(
+            read_context(&config_dir, "missing"),
+            Err(DockerContextNotFoundError { .. })
+        ));
+
+        let context_tls_dir = tls_dir.join("a1").join("docker");
+        assert_eq!(tls_files("remote", Some(&context_tls_dir)).unwrap(), None);
+        fs::write(context_tls_dir.join("ca.pem"), "").unwrap();
+        assert!(matches!(
+            tls_files("remote", Some(&context_tls_dir)),
+            Err(DockerContextUnsupportedError { reason, .. })
+                if reason == "its TLS material is missing key.pem, cert.pem"
+        ));
+        fs::write(context_tls_dir.join("key.pem"), "").unwrap();
+        fs::write(context_tls_dir.join("cert.pem"), "").unwrap();
+        assert_eq!(
+            tls_files("remote", Some(&context_tls_dir)).unwrap(),
+            Some([
+                context_tls_dir.join("key.pem"),
+                context_tls_dir.join("cert.pem"),
+                context_tls_dir.join("ca.pem"),
+            ])
+        );
+
+        fs::remove_dir_all(&config_dir).unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 use std::cmp;
+use std::collections::HashMap;
 use std::env;
 use std::fmt;
 #[cfg(feature = "ssl")]
@@ -8,9 +9,10 @@ use std::future::Future;
 use std::io;
 #[cfg(feature = "ssl")]
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 #[cfg(feature = "ct_logs")]
 use std::time::SystemTime;
@@ -19,8 +21,11 @@ use chrono::{DateTime, Utc};
 use futures_core::Stream;
 use futures_util::future::FutureExt;
 use futures_util::future::TryFutureExt;
-use futures_util::stream::TryStreamExt;
-use http::header::CONTENT_TYPE;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use http::header::{
+    HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY,
+    USER_AGENT,
+};
 use http::request::Builder;
 use hyper::client::{Client, HttpConnector};
 use hyper::{self, body::Bytes, Body, Method, Request, Response, StatusCode};
@@ -31,15 +36,22 @@ use hyperlocal::UnixConnector;
 #[cfg(feature = "ssl")]
 use rustls::sign::{CertifiedKey, RsaSigningKey};
 use tokio::io::{split, AsyncRead, AsyncWrite};
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::WebSocketStream;
 use tokio_util::codec::FramedRead;
 
+use crate::auth::DockerConfigFile;
 use crate::container::LogOutput;
 use crate::errors::Error;
 use crate::errors::Error::*;
 #[cfg(windows)]
 use crate::named_pipe::NamedPipeConnector;
 use crate::read::{JsonLineDecoder, NewlineLogOutputDecoder, StreamReader};
+use crate::retry::RetryPolicy;
+use crate::ssh::{SshConnector, DEFAULT_SSH_COMMAND};
+use crate::transport::{DockerTransport, UpgradedIo};
 use crate::uri::Uri;
+use crate::websocket::validate_accept_key;
 
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
@@ -55,6 +67,17 @@ pub const DEFAULT_NAMED_PIPE: &str = "npipe:////./pipe/docker_engine";
 /// The default `DOCKER_HOST` address that we will try to connect to.
 pub const DEFAULT_DOCKER_HOST: &str = "tcp://localhost:2375";
 
+/// The host named in request uris sent through a custom transport.
+const CUSTOM_TRANSPORT_HOST: &str = "localhost";
+
+/// The address that the [DockerBuilder] falls back to without a url or `DOCKER_HOST`.
+#[cfg(unix)]
+pub(crate) const DEFAULT_LOCAL_HOST: &str = DEFAULT_SOCKET;
+#[cfg(windows)]
+pub(crate) const DEFAULT_LOCAL_HOST: &str = DEFAULT_NAMED_PIPE;
+#[cfg(not(any(unix, windows)))]
+pub(crate) const DEFAULT_LOCAL_HOST: &str = DEFAULT_DOCKER_HOST;
+
 /// Default timeout for all requests is 2 minutes.
 const DEFAULT_TIMEOUT: u64 = 120;
 
@@ -77,6 +100,10 @@ pub(crate) enum ClientType {
     SSL,
     #[cfg(windows)]
     NamedPipe,
+    Ssh,
+    Custom {
+        scheme: &'static str,
+    },
 }
 
 /// Transport is the type representing the means of communication
@@ -100,6 +127,12 @@ pub(crate) enum Transport {
     NamedPipe {
         client: Client<NamedPipeConnector>,
     },
+    Ssh {
+        client: Client<SshConnector>,
+    },
+    Custom {
+        transport: Box<dyn DockerTransport>,
+    },
 }
 
 impl fmt::Debug for Transport {
@@ -112,6 +145,8 @@ impl fmt::Debug for Transport {
             Transport::Unix { .. } => write!(f, "Unix"),
             #[cfg(windows)]
             Transport::NamedPipe { .. } => write!(f, "NamedPipe"),
+            Transport::Ssh { .. } => write!(f, "SSH"),
+            Transport::Custom { .. } => write!(f, "Custom"),
         }
     }
 }
@@ -177,6 +212,13 @@ impl From<&(AtomicUsize, AtomicUsize)> for ClientVersion {
     }
 }
 
+/// Encode the raw data of a secret or config for the `Data` field of its spec. The daemon decodes
+/// this field as a go byte slice, which expects standard base64 rather than the url safe variant
+/// used in registry headers.
+pub(crate) fn encode_spec_data(data: &[u8]) -> String {
+    base64::encode(data)
+}
+
 pub(crate) fn serialize_as_json<T, S>(t: &T, s: S) -> Result<S::Ok, S::Error>
 where
     T: Serialize,
@@ -212,12 +254,20 @@ where
 ///  - [`Docker::connect_with_ssl_defaults`](Docker::connect_with_ssl_defaults())
 ///  - [`Docker::connect_with_unix_defaults`](Docker::connect_with_unix_defaults())
 ///  - [`Docker::connect_with_local_defaults`](Docker::connect_with_local_defaults())
+///
+/// Or configure one through a [DockerBuilder].
 pub struct Docker {
     pub(crate) transport: Arc<Transport>,
     pub(crate) client_type: ClientType,
     pub(crate) client_addr: String,
-    pub(crate) client_timeout: u64,
+    pub(crate) client_timeout: Option<Duration>,
+    pub(crate) read_timeout: Option<Duration>,
+    pub(crate) read_timeout_per_call: bool,
     pub(crate) version: Arc<(AtomicUsize, AtomicUsize)>,
+    pub(crate) headers: Arc<HeaderMap>,
+    pub(crate) retry_policy: Option<Arc<RetryPolicy>>,
+    pub(crate) identity_tokens: Arc<RwLock<HashMap<String, String>>>,
+    pub(crate) docker_config: Option<Arc<DockerConfigFile>>,
 }
 
 impl Clone for Docker {
@@ -227,11 +277,480 @@ impl Clone for Docker {
             client_type: self.client_type.clone(),
             client_addr: self.client_addr.clone(),
             client_timeout: self.client_timeout,
+            read_timeout: self.read_timeout,
+            read_timeout_per_call: self.read_timeout_per_call,
             version: self.version.clone(),
+            headers: self.headers.clone(),
+            retry_policy: self.retry_policy.clone(),
+            identity_tokens: self.identity_tokens.clone(),
+            docker_config: self.docker_config.clone(),
+        }
+    }
+}
+
+/// ---
+///
+/// # DockerBuilder
+///
+/// Builder for a [Docker] client, which selects the transport from the scheme of the connection
+/// url:
+///  - `unix://` connects to a Unix socket.
+///  - `npipe://` connects to a Windows named pipe.
+///  - `http://` connects over unsecured HTTP.
+///  - `https://` connects over HTTPS, using the `ssl` feature.
+///  - `tcp://` connects over HTTPS if any TLS material is configured or the `DOCKER_TLS_VERIFY`
+///    environment variable is set, and unsecured HTTP otherwise. Without the `ssl` feature,
+///    building a client that requires TLS fails.
+///  - `ssh://[user@]host[:port]` runs `docker system dial-stdio` on the host through `ssh`.
+///
+/// If no url is given, the `DOCKER_HOST` environment variable is used, falling back to the local
+/// unix socket or named pipe.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use bollard::{DockerBuilder, API_DEFAULT_VERSION};
+///
+/// use std::time::Duration;
+///
+/// let docker = DockerBuilder::new()
+///     .url("tcp://localhost:2375")
+///     .timeout(Duration::from_secs(30))
+///     .client_version(API_DEFAULT_VERSION)
+///     .user_agent("my-app/1.0")
+///     .pool_idle_timeout(Duration::from_secs(60))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DockerBuilder {
+    url: Option<String>,
+    timeout: Duration,
+    read_timeout: Option<Duration>,
+    client_version: ClientVersion,
+    headers: HeaderMap,
+    user_agent: Option<String>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    docker_config: Option<DockerConfigFile>,
+    ssh_command: String,
+    tls_verify_from_env: bool,
+    #[cfg(feature = "ssl")]
+    ssl_key: Option<PathBuf>,
+    #[cfg(feature = "ssl")]
+    ssl_cert: Option<PathBuf>,
+    #[cfg(feature = "ssl")]
+    ssl_ca: Option<PathBuf>,
+}
+
+impl Default for DockerBuilder {
+    fn default() -> DockerBuilder {
+        DockerBuilder {
+            url: None,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+            read_timeout: None,
+            client_version: *API_DEFAULT_VERSION,
+            headers: HeaderMap::new(),
+            user_agent: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            retry_policy: None,
+            docker_config: None,
+            ssh_command: DEFAULT_SSH_COMMAND.to_string(),
+            tls_verify_from_env: true,
+            #[cfg(feature = "ssl")]
+            ssl_key: None,
+            #[cfg(feature = "ssl")]
+            ssl_cert: None,
+            #[cfg(feature = "ssl")]
+            ssl_ca: None,
         }
     }
 }
 
+impl DockerBuilder {
+    /// Create a builder with the default timeout of 2 minutes and the default client version.
+    pub fn new() -> DockerBuilder {
+        DockerBuilder::default()
+    }
+
+    /// Set the connection url, including the scheme that selects the transport.
+    pub fn url(mut self, url: &str) -> DockerBuilder {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    /// Set the timeout for receiving the response headers of each request. Streamed response
+    /// bodies are bounded by [`DockerBuilder::read_timeout`] instead.
+    pub fn timeout(mut self, timeout: Duration) -> DockerBuilder {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set how long a streamed response body may go without receiving data, for example while
+    /// exporting a container. Streams have no idle timeout by default.
+    ///
+    /// Streams that may stay silent for as long as the daemon waits or works are exempt:
+    ///
+    ///  - [`Docker::wait_container`](Docker::wait_container()).
+    ///  - [`Docker::logs`](Docker::logs()), [`Docker::service_logs`](Docker::service_logs()) and
+    ///    [`Docker::task_logs`](Docker::task_logs()) when following the logs.
+    ///  - [`Docker::events`](Docker::events()).
+    ///  - [`Docker::create_image`](Docker::create_image()),
+    ///    [`Docker::import_image`](Docker::import_image()),
+    ///    [`Docker::push_image`](Docker::push_image()) and
+    ///    [`Docker::build_image`](Docker::build_image()).
+    ///  - [`Docker::install_plugin`](Docker::install_plugin()),
+    ///    [`Docker::upgrade_plugin`](Docker::upgrade_plugin()) and
+    ///    [`Docker::push_plugin`](Docker::push_plugin()).
+    ///
+    /// Set an idle timeout for such a call with
+    /// [`Docker::with_read_timeout`](Docker::with_read_timeout()). Connections upgraded to
+    /// another protocol, such as attached containers, never time out.
+    pub fn read_timeout(mut self, timeout: Duration) -> DockerBuilder {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the client version to communicate with the server.
+    pub fn client_version(mut self, client_version: &ClientVersion) -> DockerBuilder {
+        self.client_version = *client_version;
+        self
+    }
+
+    /// Add a header that is sent with every request, unless the request sets that header itself.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> DockerBuilder {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Set the `User-Agent` header that is sent with every request.
+    pub fn user_agent(mut self, user_agent: &str) -> DockerBuilder {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Set the maximum number of idle connections kept per host. Unix sockets and named pipes
+    /// default to `0`, other transports to hyper's default.
+    pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> DockerBuilder {
+        self.pool_max_idle_per_host = Some(max_idle);
+        self
+    }
+
+    /// Set how long an idle connection is kept in the pool.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> DockerBuilder {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the policy to retry requests that failed with a transient error. Requests are not
+    /// retried by default.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> DockerBuilder {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Fall back to the credentials of a docker CLI configuration file when pulling, pushing or
+    /// building images without explicit credentials. By default, only the credentials passed to
+    /// a call are sent.
+    pub fn docker_config(mut self, docker_config: DockerConfigFile) -> DockerBuilder {
+        self.docker_config = Some(docker_config);
+        self
+    }
+
+    /// Set the program run to reach an `ssh://` url, which defaults to `ssh`. It is called with
+    /// the same arguments as `ssh`, and must relay the daemon socket over its stdio.
+    pub fn ssh_command(mut self, command: &str) -> DockerBuilder {
+        self.ssh_command = command.to_string();
+        self
+    }
+
+    /// Set the private key path. Defaults to `key.pem` in the `DOCKER_CERT_PATH` directory, which
+    /// is also used for a `tcp://` url when only `DOCKER_TLS_VERIFY` is set.
+    #[cfg(feature = "ssl")]
+    pub fn ssl_key(mut self, ssl_key: &Path) -> DockerBuilder {
+        self.ssl_key = Some(ssl_key.to_owned());
+        self
+    }
+
+    /// Set the server certificate path. Defaults to `cert.pem` in the `DOCKER_CERT_PATH`
+    /// directory.
+    #[cfg(feature = "ssl")]
+    pub fn ssl_cert(mut self, ssl_cert: &Path) -> DockerBuilder {
+        self.ssl_cert = Some(ssl_cert.to_owned());
+        self
+    }
+
+    /// Set the certificate chain path. Defaults to `ca.pem` in the `DOCKER_CERT_PATH` directory.
+    #[cfg(feature = "ssl")]
+    pub fn ssl_ca(mut self, ssl_ca: &Path) -> DockerBuilder {
+        self.ssl_ca = Some(ssl_ca.to_owned());
+        self
+    }
+
+    /// Build the [Docker] client, choosing the transport from the connection url scheme.
+    pub fn build(self) -> Result<Docker, Error> {
+        let url = match self.url {
+            Some(ref url) => url.to_owned(),
+            None => env::var("DOCKER_HOST").unwrap_or_else(|_| DEFAULT_LOCAL_HOST.to_string()),
+        };
+        let tls_verify = if self.tls_verify_from_env {
+            env::var("DOCKER_TLS_VERIFY").ok()
+        } else {
+            None
+        };
+        let tls = requires_tls(self.has_ssl_material(), tls_verify.as_deref());
+
+        match url.split_once("://").map(|(scheme, _)| scheme) {
+            #[cfg(unix)]
+            Some("unix") => self.connect_unix(&url),
+            #[cfg(windows)]
+            Some("npipe") => self.connect_named_pipe(&url),
+            #[cfg(feature = "ssl")]
+            Some("https") => self.connect_ssl(&url),
+            #[cfg(feature = "ssl")]
+            Some("tcp") if tls => self.connect_ssl(&url),
+            #[cfg(not(feature = "ssl"))]
+            Some("tcp") if tls => Err(SslFeatureDisabledError { url }),
+            Some("tcp") | Some("http") => self.connect_http(&url),
+            Some("ssh") => self.connect_ssh(&url),
+            _ => Err(UnsupportedURLSchemeError { url }),
+        }
+    }
+
+    /// Decide on TLS for a `tcp://` url from the configured TLS material only, ignoring
+    /// `DOCKER_TLS_VERIFY`, as for a docker context that brings its own TLS settings.
+    pub(crate) fn ignore_tls_verify_env(mut self) -> DockerBuilder {
+        self.tls_verify_from_env = false;
+        self
+    }
+
+    /// Build the [Docker] client over a custom [DockerTransport]. Requests are addressed to the
+    /// connection url in the form its scheme's connector expects, for example `unix://` urls to
+    /// a `hyperlocal::UnixConnector`, and to `http://localhost` without a url.
+    pub fn build_with_transport<T>(self, transport: T) -> Result<Docker, Error>
+    where
+        T: DockerTransport + 'static,
+    {
+        let (client_type, client_addr) = match self.url {
+            Some(ref url) => custom_transport_target(url)?,
+            None => (
+                ClientType::Custom { scheme: "http" },
+                String::from(CUSTOM_TRANSPORT_HOST),
+            ),
+        };
+        self.into_docker(
+            Transport::Custom {
+                transport: Box::new(transport),
+            },
+            client_type,
+            client_addr,
+        )
+    }
+
+    #[cfg(feature = "ssl")]
+    fn has_ssl_material(&self) -> bool {
+        self.ssl_key.is_some() || self.ssl_cert.is_some() || self.ssl_ca.is_some()
+    }
+
+    #[cfg(not(feature = "ssl"))]
+    fn has_ssl_material(&self) -> bool {
+        false
+    }
+
+    fn client_builder(&self, default_max_idle: Option<usize>) -> hyper::client::Builder {
+        let mut client_builder = Client::builder();
+        if let Some(max_idle) = self.pool_max_idle_per_host.or(default_max_idle) {
+            client_builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(idle_timeout) = self.pool_idle_timeout {
+            client_builder.pool_idle_timeout(idle_timeout);
+        }
+        client_builder
+    }
+
+    fn into_docker(
+        self,
+        transport: Transport,
+        client_type: ClientType,
+        client_addr: String,
+    ) -> Result<Docker, Error> {
+        let mut headers = self.headers;
+        if let Some(ref user_agent) = self.user_agent {
+            headers.insert(
+                USER_AGENT,
+                HeaderValue::from_str(user_agent).map_err(http::Error::from)?,
+            );
+        }
+
+        Ok(Docker {
+            transport: Arc::new(transport),
+            client_type,
+            client_addr,
+            client_timeout: Some(self.timeout),
+            read_timeout: self.read_timeout,
+            read_timeout_per_call: false,
+            version: Arc::new((
+                AtomicUsize::new(self.client_version.major_version),
+                AtomicUsize::new(self.client_version.minor_version),
+            )),
+            headers: Arc::new(headers),
+            retry_policy: self.retry_policy.map(Arc::new),
+            identity_tokens: Arc::new(RwLock::new(HashMap::new())),
+            docker_config: self.docker_config.map(Arc::new),
+        })
+    }
+
+    #[cfg(feature = "ssl")]
+    fn connect_ssl(self, addr: &str) -> Result<Docker, Error> {
+        // This ensures that using docker-machine-esque addresses work with Hyper.
+        let client_addr = addr.replacen("tcp://", "", 1).replacen("https://", "", 1);
+
+        let (ssl_key, ssl_cert, ssl_ca) = match (&self.ssl_key, &self.ssl_cert, &self.ssl_ca) {
+            (Some(key), Some(cert), Some(ca)) => (key.to_owned(), cert.to_owned(), ca.to_owned()),
+            (key, cert, ca) => {
+                let cert_path = DockerClientCertResolver::default_cert_path()?;
+                (
+                    key.to_owned().unwrap_or_else(|| cert_path.join("key.pem")),
+                    cert.to_owned()
+                        .unwrap_or_else(|| cert_path.join("cert.pem")),
+                    ca.to_owned().unwrap_or_else(|| cert_path.join("ca.pem")),
+                )
+            }
+        };
+
+        let mut root_store = rustls::RootCertStore::empty();
+        for cert in rustls_native_certs::load_native_certs()? {
+            root_store
+                .add(&rustls::Certificate(cert.0))
+                .map_err(|err| NoNativeCertsError { err })?;
+        }
+
+        root_store.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
+            rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+                ta.subject,
+                ta.spki,
+                ta.name_constraints,
+            )
+        }));
+
+        let mut ca_pem = io::Cursor::new(fs::read(&ssl_ca).map_err(|_| CertPathError {
+            path: ssl_ca.to_owned(),
+        })?);
+
+        root_store.add_parsable_certificates(&rustls_pemfile::certs(&mut ca_pem).map_err(
+            |_| CertParseError {
+                path: ssl_ca.to_owned(),
+            },
+        )?);
+
+        #[cfg(feature = "ct_logs")]
+        let config = {
+            let ct_logs_expiry =
+                SystemTime::UNIX_EPOCH + Duration::from_secs(TIMESTAMP_CT_LOGS_EXPIRY);
+            rustls::ClientConfig::builder()
+                .with_safe_defaults()
+                .with_root_certificates(root_store)
+                .with_certificate_transparency_logs(&ct_logs::LOGS, ct_logs_expiry)
+                .with_client_cert_resolver(Arc::new(DockerClientCertResolver {
+                    ssl_key: ssl_key.to_owned(),
+                    ssl_cert: ssl_cert.to_owned(),
+                }))
+        };
+        #[cfg(not(feature = "ct_logs"))]
+        let config = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(root_store)
+            .with_client_cert_resolver(Arc::new(DockerClientCertResolver {
+                ssl_key: ssl_key.to_owned(),
+                ssl_cert: ssl_cert.to_owned(),
+            }));
+
+        let mut http_connector = HttpConnector::new();
+        http_connector.enforce_http(false);
+
+        let https_connector: HttpsConnector<HttpConnector> =
+            HttpsConnector::from((http_connector, config));
+
+        let client = self.client_builder(None).build(https_connector);
+        self.into_docker(Transport::Https { client }, ClientType::SSL, client_addr)
+    }
+
+    fn connect_http(self, addr: &str) -> Result<Docker, Error> {
+        // This ensures that using docker-machine-esque addresses work with Hyper.
+        let client_addr = addr.replacen("tcp://", "", 1).replacen("http://", "", 1);
+
+        let http_connector = HttpConnector::new();
+
+        let client = self.client_builder(None).build(http_connector);
+        self.into_docker(Transport::Http { client }, ClientType::Http, client_addr)
+    }
+
+    fn connect_ssh(self, addr: &str) -> Result<Docker, Error> {
+        let ssh_connector = SshConnector::new(&self.ssh_command, addr)?;
+
+        let client = self.client_builder(None).build(ssh_connector);
+        self.into_docker(Transport::Ssh { client }, ClientType::Ssh, addr.to_string())
+    }
+
+    #[cfg(unix)]
+    fn connect_unix(self, path: &str) -> Result<Docker, Error> {
+        let client_addr = path.replacen("unix://", "", 1);
+
+        let unix_connector = UnixConnector;
+
+        let client = self.client_builder(Some(0)).build(unix_connector);
+        self.into_docker(Transport::Unix { client }, ClientType::Unix, client_addr)
+    }
+
+    #[cfg(windows)]
+    fn connect_named_pipe(self, path: &str) -> Result<Docker, Error> {
+        let client_addr = path.replacen("npipe://", "", 1);
+
+        let named_pipe_connector = NamedPipeConnector;
+
+        let mut client_builder = self.client_builder(Some(0));
+        client_builder.http1_title_case_headers(true);
+        let client = client_builder.build(named_pipe_connector);
+        self.into_docker(
+            Transport::NamedPipe { client },
+            ClientType::NamedPipe,
+            client_addr,
+        )
+    }
+}
+
+/// The client type and address of request uris sent through a custom transport to a connection
+/// url.
+fn custom_transport_target(url: &str) -> Result<(ClientType, String), Error> {
+    let (scheme, addr) = url
+        .split_once("://")
+        .ok_or_else(|| UnsupportedURLSchemeError {
+            url: url.to_string(),
+        })?;
+    let scheme = match scheme {
+        "unix" => "unix",
+        "npipe" => "net.pipe",
+        "tcp" | "http" => "http",
+        "https" => "https",
+        _ => {
+            return Err(UnsupportedURLSchemeError {
+                url: url.to_string(),
+            })
+        }
+    };
+    Ok((ClientType::Custom { scheme }, addr.to_string()))
+}
+
+/// Whether a `tcp://` url is reached over TLS, given whether any TLS material was configured on
+/// the builder and the value of `DOCKER_TLS_VERIFY`, which like the docker CLI enables TLS when
+/// set to any non-empty value.
+#[allow(clippy::unnecessary_map_or)]
+fn requires_tls(has_ssl_material: bool, tls_verify: Option<&str>) -> bool {
+    has_ssl_material || tls_verify.map_or(false, |v| !v.is_empty())
+}
+
 #[cfg(feature = "ssl")]
 struct DockerClientCertResolver {
     ssl_key: PathBuf,
@@ -398,75 +917,13 @@ impl Docker {
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        // This ensures that using docker-machine-esque addresses work with Hyper.
-        let client_addr = addr.replacen("tcp://", "", 1).replacen("https://", "", 1);
-
-        let mut root_store = rustls::RootCertStore::empty();
-        for cert in rustls_native_certs::load_native_certs()? {
-            root_store.add(&rustls::Certificate(cert.0)).map_err(|err| NoNativeCertsError{ err })?;
-        }
-
-        root_store.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
-            rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
-                ta.subject,
-                ta.spki,
-                ta.name_constraints,
-            )
-        }));
-
-        let mut ca_pem = io::Cursor::new(fs::read(ssl_ca).map_err(|_| CertPathError {
-            path: ssl_ca.to_owned(),
-        })?);
-
-        root_store.add_parsable_certificates(&rustls_pemfile::certs(&mut ca_pem).map_err(
-            |_| CertParseError {
-                path: ssl_ca.to_owned(),
-            },
-        )?);
-
-        #[cfg(feature = "ct_logs")]
-        let config = {
-            let ct_logs_expiry =
-                SystemTime::UNIX_EPOCH + Duration::from_secs(TIMESTAMP_CT_LOGS_EXPIRY);
-            rustls::ClientConfig::builder()
-                .with_safe_defaults()
-                .with_root_certificates(root_store)
-                .with_certificate_transparency_logs(&ct_logs::LOGS, ct_logs_expiry)
-                .with_client_cert_resolver(Arc::new(DockerClientCertResolver {
-                    ssl_key: ssl_key.to_owned(),
-                    ssl_cert: ssl_cert.to_owned(),
-                }))
-        };
-        #[cfg(not(feature = "ct_logs"))]
-        let config = rustls::ClientConfig::builder()
-            .with_safe_defaults()
-            .with_root_certificates(root_store)
-            .with_client_cert_resolver(Arc::new(DockerClientCertResolver {
-                ssl_key: ssl_key.to_owned(),
-                ssl_cert: ssl_cert.to_owned(),
-            }));
-
-        let mut http_connector = HttpConnector::new();
-        http_connector.enforce_http(false);
-
-        let https_connector: HttpsConnector<HttpConnector> =
-            HttpsConnector::from((http_connector, config));
-
-        let client_builder = Client::builder();
-        let client = client_builder.build(https_connector);
-        let transport = Transport::Https { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::SSL,
-            client_addr,
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
-
-        Ok(docker)
+        DockerBuilder::new()
+            .timeout(Duration::from_secs(timeout))
+            .client_version(client_version)
+            .ssl_key(ssl_key)
+            .ssl_cert(ssl_cert)
+            .ssl_ca(ssl_ca)
+            .connect_ssl(addr)
     }
 }
 
@@ -477,7 +934,7 @@ impl Docker {
     /// # Defaults
     ///
     ///  - The connection url is sourced from the `DOCKER_HOST` environment variable, and defaults
-    ///  to `localhost:2375`.
+    ///    to `localhost:2375`.
     ///  - The number of threads used for the HTTP connection pool defaults to 1.
     ///  - The request timeout defaults to 2 minutes.
     ///
@@ -523,26 +980,10 @@ impl Docker {
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        // This ensures that using docker-machine-esque addresses work with Hyper.
-        let client_addr = addr.replacen("tcp://", "", 1).replacen("http://", "", 1);
-
-        let http_connector = HttpConnector::new();
-
-        let client_builder = Client::builder();
-        let client = client_builder.build(http_connector);
-        let transport = Transport::Http { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::Http,
-            client_addr,
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
-
-        Ok(docker)
+        DockerBuilder::new()
+            .timeout(Duration::from_secs(timeout))
+            .client_version(client_version)
+            .connect_http(addr)
     }
 
     /// Connect using to either a Unix socket or a Windows named pipe using defaults common to the
@@ -551,7 +992,7 @@ impl Docker {
     /// # Defaults
     ///
     ///  - The unix socket location defaults to `/var/run/docker.sock`. The windows named pipe
-    ///  location defaults to `//./pipe/docker_engine`.
+    ///    location defaults to `//./pipe/docker_engine`.
     ///  - The request timeout defaults to 2 minutes.
     ///
     /// # Examples
@@ -663,27 +1104,10 @@ impl Docker {
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        let client_addr = path.replacen("unix://", "", 1);
-
-        let unix_connector = UnixConnector;
-
-        let mut client_builder = Client::builder();
-        client_builder.pool_max_idle_per_host(0);
-
-        let client = client_builder.build(unix_connector);
-        let transport = Transport::Unix { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::Unix,
-            client_addr,
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
-
-        Ok(docker)
+        DockerBuilder::new()
+            .timeout(Duration::from_secs(timeout))
+            .client_version(client_version)
+            .connect_unix(path)
     }
 }
 
@@ -739,27 +1163,55 @@ impl Docker {
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        let client_addr = path.replacen("npipe://", "", 1);
-
-        let named_pipe_connector = NamedPipeConnector;
-
-        let mut client_builder = Client::builder();
-        client_builder.pool_max_idle_per_host(0);
-        client_builder.http1_title_case_headers(true);
-        let client = client_builder.build(named_pipe_connector);
-        let transport = Transport::NamedPipe { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::NamedPipe,
-            client_addr,
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
+        DockerBuilder::new()
+            .timeout(Duration::from_secs(timeout))
+            .client_version(client_version)
+            .connect_named_pipe(path)
+    }
+}
 
-        Ok(docker)
+/// A Docker implementation typed to communicate over a custom transport.
+impl Docker {
+    /// Connect using a custom [DockerTransport], such as a hyper client over another connector,
+    /// or a fake serving canned responses.
+    ///
+    /// # Arguments
+    ///
+    ///  - `transport`: the transport sending requests to the daemon.
+    ///  - `addr`: connection url including scheme, which addresses requests the way the
+    ///    transport's connector expects, e.g. `unix:///var/run/docker.sock` for a
+    ///    `hyperlocal::UnixConnector` or `tcp://localhost:2375` for an `HttpConnector`.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every request.
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use futures_util::future::TryFutureExt;
+    /// use hyper::client::{Client, HttpConnector};
+    ///
+    /// let client = Client::builder().build(HttpConnector::new());
+    /// let connection =
+    ///     Docker::connect_with_transport(client, "tcp://localhost:2375", 120, API_DEFAULT_VERSION)
+    ///         .unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_transport<T>(
+        transport: T,
+        addr: &str,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error>
+    where
+        T: DockerTransport + 'static,
+    {
+        DockerBuilder::new()
+            .url(addr)
+            .timeout(Duration::from_secs(timeout))
+            .client_version(client_version)
+            .build_with_transport(transport)
     }
 }
 
@@ -817,6 +1269,16 @@ impl Docker {
         }
     }
 
+    /// This client, for a call whose stream may receive no data for as long as the daemon waits,
+    /// such as following logs. The idle timeout of the client does not apply to such a stream,
+    /// unless it was set for the call with [`Docker::with_read_timeout`].
+    pub(crate) fn for_silent_stream(&self) -> Docker {
+        Docker {
+            read_timeout: self.read_timeout.filter(|_| self.read_timeout_per_call),
+            ..self.clone()
+        }
+    }
+
     pub(crate) fn process_into_stream<T>(
         &self,
         req: Result<Request<Body>, Error>,
@@ -824,9 +1286,10 @@ impl Docker {
     where
         T: DeserializeOwned,
     {
+        let read_timeout = self.read_timeout;
         Box::pin(
             self.process_request(req)
-                .map_ok(Docker::decode_into_stream::<T>)
+                .map_ok(move |response| Docker::decode_into_stream::<T>(response, read_timeout))
                 .into_stream()
                 .try_flatten(),
         )
@@ -836,9 +1299,10 @@ impl Docker {
         &self,
         req: Result<Request<Body>, Error>,
     ) -> impl Stream<Item = Result<LogOutput, Error>> + Unpin {
+        let read_timeout = self.read_timeout;
         Box::pin(
             self.process_request(req)
-                .map_ok(Docker::decode_into_stream_string)
+                .map_ok(move |response| Docker::decode_into_stream_string(response, read_timeout))
                 .try_flatten_stream(),
         )
     }
@@ -858,14 +1322,26 @@ impl Docker {
         &self,
         req: Result<Request<Body>, Error>,
     ) -> impl Stream<Item = Result<Bytes, Error>> + Unpin {
+        let read_timeout = self.read_timeout;
         Box::pin(
             self.process_request(req)
-                .map_ok(|response| response.into_body().map_err(Error::from))
+                .map_ok(move |response| Docker::body_stream(response, read_timeout))
                 .into_stream()
                 .try_flatten(),
         )
     }
 
+    pub(crate) fn process_into_headers(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Future<Output = Result<HeaderMap, Error>> {
+        let fut = self.process_request(req);
+        async move {
+            let response = fut.await?;
+            Ok(response.headers().clone())
+        }
+    }
+
     pub(crate) fn process_into_string(
         &self,
         req: Result<Request<Body>, Error>,
@@ -882,10 +1358,39 @@ impl Docker {
         req: Result<Request<Body>, Error>,
     ) -> Result<(impl AsyncRead, impl AsyncWrite), Error> {
         let res = self.process_request(req).await?;
-        let upgraded = hyper::upgrade::on(res).await?;
+        let upgraded = self.upgrade(res).await?;
         Ok(split(upgraded))
     }
 
+    pub(crate) async fn process_into_websocket(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> Result<WebSocketStream<Box<dyn UpgradedIo>>, Error> {
+        let key = req
+            .as_ref()
+            .ok()
+            .and_then(|req| req.headers().get(SEC_WEBSOCKET_KEY))
+            .map(|key| key.as_bytes().to_vec());
+        let res = self.process_request(req).await?;
+        if let Some(key) = key {
+            validate_accept_key(
+                &key,
+                res.headers()
+                    .get(SEC_WEBSOCKET_ACCEPT)
+                    .map(HeaderValue::as_bytes),
+            )?;
+        }
+        let upgraded = self.upgrade(res).await?;
+        Ok(WebSocketStream::from_raw_socket(upgraded, Role::Client, None).await)
+    }
+
+    async fn upgrade(&self, res: Response<Body>) -> Result<Box<dyn UpgradedIo>, Error> {
+        match *self.transport {
+            Transport::Custom { ref transport } => transport.upgrade(res).await,
+            _ => Ok(Box::new(hyper::upgrade::on(res).await?)),
+        }
+    }
+
     pub(crate) fn serialize_payload<S>(body: Option<S>) -> Result<Body, Error>
     where
         S: Serialize,
@@ -908,6 +1413,97 @@ impl Docker {
         self.version.as_ref().into()
     }
 
+    /// Return a client sharing this client's connection, which retries requests according to the
+    /// given policy. Useful to retry a single call, or to opt in a call with a method that is not
+    /// retried otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{Docker, RetryPolicy};
+    ///
+    /// let docker = Docker::connect_with_local_defaults().unwrap();
+    /// async move {
+    ///     docker
+    ///         .with_retry_policy(RetryPolicy::new().max_attempts(10))
+    ///         .inspect_container("my-container", None)
+    ///         .await
+    /// };
+    /// ```
+    pub fn with_retry_policy(&self, retry_policy: RetryPolicy) -> Docker {
+        Docker {
+            retry_policy: Some(Arc::new(retry_policy)),
+            ..self.clone()
+        }
+    }
+
+    /// Return a client sharing this client's connection, which waits for the response headers of
+    /// each request for the given duration instead of the client timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use std::time::Duration;
+    ///
+    /// let docker = Docker::connect_with_local_defaults().unwrap();
+    /// async move {
+    ///     docker
+    ///         .with_timeout(Duration::from_secs(5))
+    ///         .inspect_container("my-container", None)
+    ///         .await
+    /// };
+    /// ```
+    pub fn with_timeout(&self, timeout: Duration) -> Docker {
+        Docker {
+            client_timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// Return a client sharing this client's connection, which fails a streamed response that
+    /// receives no data for the given duration. Unlike the idle timeout set with
+    /// [`DockerBuilder::read_timeout`], this also applies to streams that may stay silent for as
+    /// long as the daemon waits, such as following logs.
+    pub fn with_read_timeout(&self, timeout: Duration) -> Docker {
+        Docker {
+            read_timeout: Some(timeout),
+            read_timeout_per_call: true,
+            ..self.clone()
+        }
+    }
+
+    /// Return a client sharing this client's connection, which never times out waiting for a
+    /// response or streamed data. Useful for calls that may legitimately take long to answer,
+    /// such as waiting for a container to exit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    /// use bollard::container::WaitContainerOptions;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// let docker = Docker::connect_with_local_defaults().unwrap();
+    /// async move {
+    ///     docker
+    ///         .without_timeout()
+    ///         .wait_container("my-container", None::<WaitContainerOptions<String>>)
+    ///         .try_collect::<Vec<_>>()
+    ///         .await
+    /// };
+    /// ```
+    pub fn without_timeout(&self) -> Docker {
+        Docker {
+            client_timeout: None,
+            read_timeout: None,
+            read_timeout_per_call: false,
+            ..self.clone()
+        }
+    }
+
     /// Check with the server for a supported version, and downgrade the client version if
     /// appropriate.
     ///
@@ -961,12 +1557,18 @@ impl Docker {
     ) -> impl Future<Output = Result<Response<Body>, Error>> {
         let transport = self.transport.clone();
         let timeout = self.client_timeout;
+        let retry_policy = self.retry_policy.clone();
 
         debug!("request: {:?}", request.as_ref().unwrap());
 
         async move {
             let request = request?;
-            let response = Docker::execute_request(transport, request, timeout).await?;
+            let response = match retry_policy {
+                Some(ref policy) if policy.applies_to(&request) => {
+                    Docker::execute_request_with_retry(transport, request, timeout, policy).await?
+                }
+                _ => Docker::execute_request(transport, request, timeout).await?,
+            };
 
             let status = response.status();
             match status {
@@ -1030,49 +1632,137 @@ impl Docker {
         )?;
         let request_uri: hyper::Uri = uri.into();
         debug!("{}", &request_uri);
-        Ok(builder
+        let mut builder = builder
             .uri(request_uri)
-            .header(CONTENT_TYPE, "application/json")
-            .body(payload?)?)
+            .header(CONTENT_TYPE, "application/json");
+        if let Some(headers) = builder.headers_mut() {
+            for name in self.headers.keys() {
+                if !headers.contains_key(name) {
+                    for value in self.headers.get_all(name) {
+                        headers.append(name.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        Ok(builder.body(payload?)?)
     }
 
     async fn execute_request(
         transport: Arc<Transport>,
         req: Request<Body>,
-        timeout: u64,
+        timeout: Option<Duration>,
     ) -> Result<Response<Body>, Error> {
         // This is where we determine to which transport we issue the request.
         let request = match *transport {
-            Transport::Http { ref client } => client.request(req),
+            Transport::Http { ref client } => DockerTransport::request(client, req),
             #[cfg(feature = "ssl")]
-            Transport::Https { ref client } => client.request(req),
+            Transport::Https { ref client } => DockerTransport::request(client, req),
             #[cfg(unix)]
-            Transport::Unix { ref client } => client.request(req),
+            Transport::Unix { ref client } => DockerTransport::request(client, req),
             #[cfg(windows)]
-            Transport::NamedPipe { ref client } => client.request(req),
+            Transport::NamedPipe { ref client } => DockerTransport::request(client, req),
+            Transport::Ssh { ref client } => DockerTransport::request(client, req),
+            Transport::Custom { ref transport } => transport.request(req),
         };
 
-        match tokio::time::timeout(Duration::from_secs(timeout), request).await {
-            Ok(v) => Ok(v?),
-            Err(_) => Err(RequestTimeoutError),
+        match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, request).await {
+                Ok(v) => Ok(v?),
+                Err(_) => Err(RequestTimeoutError),
+            },
+            None => request.await,
+        }
+    }
+
+    /// Send a request until it succeeds, fails with an error the policy does not retry, or runs
+    /// out of attempts. Retryable responses are only returned from the last attempt.
+    async fn execute_request_with_retry(
+        transport: Arc<Transport>,
+        req: Request<Body>,
+        timeout: Option<Duration>,
+        policy: &RetryPolicy,
+    ) -> Result<Response<Body>, Error> {
+        let (parts, body) = req.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+
+        let mut attempt = 1;
+        loop {
+            let mut builder = Request::builder()
+                .method(parts.method.clone())
+                .uri(parts.uri.clone())
+                .version(parts.version);
+            if let Some(headers) = builder.headers_mut() {
+                *headers = parts.headers.clone();
+            }
+            let req = builder.body(Body::from(body.clone()))?;
+
+            let result = Docker::execute_request(transport.clone(), req, timeout).await;
+            let retry = match result {
+                Ok(ref response) => policy.is_retryable_status(response.status()),
+                Err(ref e) => policy.is_retryable_error(e),
+            };
+            if !retry || !policy.has_attempts_left(attempt) {
+                return result;
+            }
+
+            let backoff = policy.backoff(attempt);
+            match result {
+                Ok(response) => debug!(
+                    "retrying {} {} in {:?} after status {}",
+                    parts.method,
+                    parts.uri,
+                    backoff,
+                    response.status()
+                ),
+                Err(e) => debug!(
+                    "retrying {} {} in {:?} after error: {}",
+                    parts.method, parts.uri, backoff, e
+                ),
+            }
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    /// The chunks of a response body, failing with a timeout error if no chunk arrives within the
+    /// read timeout.
+    fn body_stream(
+        res: Response<Body>,
+        read_timeout: Option<Duration>,
+    ) -> Pin<Box<dyn Stream<Item = Result<Bytes, Error>> + Send>> {
+        let body = res.into_body();
+        match read_timeout {
+            None => Box::pin(body.map_err(Error::from)),
+            Some(read_timeout) => Box::pin(stream::unfold(Some(body), move |body| async move {
+                let mut body = body?;
+                match tokio::time::timeout(read_timeout, body.next()).await {
+                    Ok(Some(chunk)) => Some((chunk.map_err(Error::from), Some(body))),
+                    Ok(None) => None,
+                    Err(_) => Some((Err(RequestTimeoutError), None)),
+                }
+            })),
         }
     }
 
-    fn decode_into_stream<T>(res: Response<Body>) -> impl Stream<Item = Result<T, Error>>
+    fn decode_into_stream<T>(
+        res: Response<Body>,
+        read_timeout: Option<Duration>,
+    ) -> impl Stream<Item = Result<T, Error>>
     where
         T: DeserializeOwned,
     {
         FramedRead::new(
-            StreamReader::new(res.into_body().map_err(Error::from)),
+            StreamReader::new(Docker::body_stream(res, read_timeout)),
             JsonLineDecoder::new(),
         )
     }
 
     fn decode_into_stream_string(
         res: Response<Body>,
+        read_timeout: Option<Duration>,
     ) -> impl Stream<Item = Result<LogOutput, Error>> {
         FramedRead::new(
-            StreamReader::new(res.into_body().map_err(Error::from)),
+            StreamReader::new(Docker::body_stream(res, read_timeout)),
             NewlineLogOutputDecoder::new(),
         )
     }
@@ -1103,3 +1793,275 @@ impl Docker {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_transport_from_scheme() {
+        let docker = DockerBuilder::new()
+            .url("tcp://localhost:2375")
+            .build()
+            .unwrap();
+        assert!(matches!(docker.client_type, ClientType::Http));
+        assert_eq!(docker.client_addr, "localhost:2375");
+
+        #[cfg(unix)]
+        {
+            let docker = DockerBuilder::new()
+                .url("unix:///var/run/docker.sock")
+                .build()
+                .unwrap();
+            assert!(matches!(docker.client_type, ClientType::Unix));
+            assert_eq!(docker.client_addr, "/var/run/docker.sock");
+        }
+
+        assert!(matches!(
+            DockerBuilder::new().url("localhost:2375").build(),
+            Err(UnsupportedURLSchemeError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_requires_tls() {
+        assert!(!requires_tls(false, None));
+        assert!(!requires_tls(false, Some("")));
+        assert!(requires_tls(false, Some("1")));
+        assert!(requires_tls(false, Some("0")));
+        assert!(requires_tls(true, None));
+    }
+
+    #[test]
+    fn test_builder_default_headers() {
+        let docker = DockerBuilder::new()
+            .url("http://localhost:2375")
+            .header(
+                HeaderName::from_static("x-custom"),
+                HeaderValue::from_static("default"),
+            )
+            .user_agent("bollard-test")
+            .build()
+            .unwrap();
+
+        let req = docker
+            .build_request(
+                "/version",
+                Builder::new()
+                    .method(Method::GET)
+                    .header("x-custom", "overridden"),
+                None::<String>,
+                Ok(Body::empty()),
+            )
+            .unwrap();
+
+        assert_eq!(req.headers().get(USER_AGENT).unwrap(), "bollard-test");
+        assert_eq!(req.headers().get_all("x-custom").iter().count(), 1);
+        assert_eq!(req.headers().get("x-custom").unwrap(), "overridden");
+    }
+
+    #[tokio::test]
+    async fn test_timeout_overrides() {
+        let docker = Docker::connect_with_transport(
+            |_request: Request<Body>| async {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                Ok(Response::new(Body::from("OK")))
+            },
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            docker.with_timeout(Duration::from_millis(20)).ping().await,
+            Err(RequestTimeoutError)
+        ));
+        assert_eq!(
+            docker
+                .with_timeout(Duration::from_millis(20))
+                .without_timeout()
+                .ping()
+                .await
+                .unwrap(),
+            "OK"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_timeout() {
+        let docker = Docker::connect_with_transport(
+            |_request: Request<Body>| async {
+                let (mut sender, body) = Body::channel();
+                tokio::spawn(async move {
+                    sender.send_data(Bytes::from("first")).await.unwrap();
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    let _ = sender.send_data(Bytes::from("second")).await;
+                });
+                Ok(Response::new(body))
+            },
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        let req = || {
+            docker.build_request(
+                "/containers/my-container/export",
+                Builder::new().method(Method::GET),
+                None::<String>,
+                Ok(Body::empty()),
+            )
+        };
+
+        let chunks: Vec<Result<Bytes, Error>> = docker
+            .with_read_timeout(Duration::from_millis(20))
+            .process_into_body(req())
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].as_ref().unwrap(), "first");
+        assert!(matches!(chunks[1], Err(RequestTimeoutError)));
+
+        let chunks: Vec<Bytes> = docker.process_into_body(req()).try_collect().await.unwrap();
+        assert_eq!(chunks, vec![Bytes::from("first"), Bytes::from("second")]);
+    }
+
+    /// A client with a 20 milliseconds idle timeout, to a daemon answering every request with a
+    /// line, followed by another line after 200 milliseconds.
+    fn silent_stream_docker(line: &'static str) -> Docker {
+        DockerBuilder::new()
+            .read_timeout(Duration::from_millis(20))
+            .build_with_transport(move |_request: Request<Body>| async move {
+                let (mut sender, body) = Body::channel();
+                tokio::spawn(async move {
+                    sender.send_data(Bytes::from(line)).await.unwrap();
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    let _ = sender.send_data(Bytes::from(line)).await;
+                });
+                Ok(Response::new(body))
+            })
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_read_timeout_silent_streams() {
+        use crate::container::{LogsOptions, WaitContainerOptions};
+        use crate::image::{BuildImageOptions, CreateImageOptions};
+        use crate::plugin::InstallPluginOptions;
+        use crate::system::EventsOptions;
+
+        let docker = silent_stream_docker("{\"StatusCode\":0}\n");
+
+        let responses: Vec<_> = docker
+            .wait_container("my-container", None::<WaitContainerOptions<String>>)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(responses.len(), 2);
+
+        let events: Vec<_> = docker
+            .events(None::<EventsOptions<String>>)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+
+        let infos: Vec<_> = docker
+            .create_image(None::<CreateImageOptions<String>>, None, None)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(infos.len(), 2);
+
+        let infos: Vec<_> = docker
+            .build_image(BuildImageOptions::<String>::default(), None, None)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(infos.len(), 2);
+
+        let infos: Vec<_> = docker
+            .install_plugin(InstallPluginOptions::<String>::default(), vec![], None)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(infos.len(), 2);
+
+        let follow = Some(LogsOptions::<String> {
+            follow: true,
+            stdout: true,
+            ..Default::default()
+        });
+        let logs: Vec<_> = docker
+            .logs("my-container", follow)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 2);
+
+        let logs: Vec<_> = docker
+            .logs("my-container", None::<LogsOptions<String>>)
+            .collect()
+            .await;
+        assert!(matches!(logs.last(), Some(Err(_))));
+
+        let responses: Vec<_> = docker
+            .with_read_timeout(Duration::from_millis(20))
+            .wait_container("my-container", None::<WaitContainerOptions<String>>)
+            .collect()
+            .await;
+        assert!(matches!(responses.last(), Some(Err(_))));
+    }
+
+    #[tokio::test]
+    async fn test_read_timeout_following_task_logs() {
+        use crate::task::TaskLogsOptions;
+
+        let docker = silent_stream_docker("hello\n");
+
+        let follow = Some(TaskLogsOptions::<String> {
+            follow: true,
+            stdout: true,
+            ..Default::default()
+        });
+        let logs: Vec<_> = docker
+            .task_logs("my-task", follow)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 2);
+
+        let logs: Vec<_> = docker
+            .task_logs("my-task", None::<TaskLogsOptions<String>>)
+            .collect()
+            .await;
+        assert!(matches!(logs.last(), Some(Err(_))));
+    }
+
+    #[tokio::test]
+    async fn test_read_timeout_following_service_logs() {
+        use crate::service::ServiceLogsOptions;
+
+        let docker = silent_stream_docker("hello\n");
+
+        let follow = Some(ServiceLogsOptions::<String> {
+            follow: true,
+            stdout: true,
+            ..Default::default()
+        });
+        let logs: Vec<_> = docker
+            .service_logs("my-service", follow)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 2);
+
+        let logs: Vec<_> = docker
+            .service_logs("my-service", None::<ServiceLogsOptions<String>>)
+            .collect()
+            .await;
+        assert!(matches!(logs.last(), Some(Err(_))));
+    }
+}
@@ -66,6 +66,12 @@ pub enum Error {
         /// Message returned by the docker server.
         message: String,
     },
+    /// Error emitted when the registry does not report a digest for an image.
+    #[error("Registry did not return a digest for image: {image}")]
+    RegistryDigestMissingError {
+        /// Image reference that was inspected.
+        image: String,
+    },
     /// Error facilitating debugging failed JSON parsing.
     #[error("Failed to deserialize JSON: {message}")]
     JsonDataError {
@@ -82,6 +88,48 @@ pub enum Error {
         /// The api version returned by the server.
         api_version: String,
     },
+    /// Error emitted when no docker context exists with the given name.
+    #[error("Docker context not found: {name}")]
+    DockerContextNotFoundError {
+        /// The context name.
+        name: String,
+    },
+    /// Error emitted when a docker context uses settings that cannot be honoured.
+    #[error("Docker context {name} is not supported: {reason}")]
+    DockerContextUnsupportedError {
+        /// The context name.
+        name: String,
+        /// Why the context is not supported.
+        reason: String,
+    },
+    /// Error emitted when a docker credential helper cannot be run or fails.
+    #[error("Credential helper {helper} failed: {message}")]
+    CredentialHelperError {
+        /// The credential helper program.
+        helper: String,
+        /// The error reported by the credential helper.
+        message: String,
+    },
+    /// Error emitted when a replayed cassette holds no response for a request.
+    #[error("Cassette has no interaction left for request: {method} {uri}")]
+    CassetteInteractionNotFoundError {
+        /// The request method.
+        method: String,
+        /// The request uri.
+        uri: String,
+    },
+    /// Error emitted when a connection url uses a scheme that no available transport supports.
+    #[error("Unsupported connection url scheme: {url}")]
+    UnsupportedURLSchemeError {
+        /// The connection url.
+        url: String,
+    },
+    /// Error emitted when a connection requires TLS, but the `ssl` feature is disabled.
+    #[error("Connecting to {url} requires TLS, but the ssl feature is disabled")]
+    SslFeatureDisabledError {
+        /// The connection url.
+        url: String,
+    },
     /// Error emitted when JSON fails to serialize.
     #[error(transparent)]
     JsonSerdeError {
@@ -124,6 +172,26 @@ pub enum Error {
         #[from]
         err: hyper::Error,
     },
+    /// Error emitted when a base64 encoded value cannot be decoded.
+    #[error(transparent)]
+    Base64DecodeError {
+        /// The original error emitted.
+        #[from]
+        err: base64::DecodeError,
+    },
+    /// Error emitted when the docker server omits a header that the response requires.
+    #[error("Docker responded without the expected header: {header}")]
+    MissingHeaderError {
+        /// Name of the missing header.
+        header: String,
+    },
+    /// Error emitted when the docker server answers a websocket handshake with an accept key
+    /// that does not match the key sent.
+    #[error("Docker responded with an invalid websocket accept key: {accept_key}")]
+    WebSocketAcceptKeyError {
+        /// The `Sec-WebSocket-Accept` header of the response.
+        accept_key: String,
+    },
     /// Error emitted when a request times out.
     #[error("Timeout error")]
     RequestTimeoutError,
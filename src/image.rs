@@ -1,13 +1,13 @@
 //! Image API: creating, manipulating and pushing docker images
 use futures_core::Stream;
-use futures_util::{stream, stream::StreamExt};
+use futures_util::{stream, stream::StreamExt, TryFutureExt};
 use http::header::CONTENT_TYPE;
 use http::request::Builder;
 use hyper::{body::Bytes, Body, Method};
 use serde::Serialize;
 
 use super::Docker;
-use crate::auth::{base64_url_encode, DockerCredentials};
+use crate::auth::{base64_url_encode, query_field, with_registry_header, DockerCredentials};
 use crate::container::Config;
 use crate::errors::Error;
 use crate::models::*;
@@ -16,7 +16,7 @@ use std::cmp::Eq;
 use std::collections::HashMap;
 use std::hash::Hash;
 
-/// Parameters available for pulling an image, used in the [Create Image
+/// Parameters available for pulling or importing an image, used in the [Create Image
 /// API](Docker::create_image)
 ///
 /// ## Examples
@@ -60,6 +60,15 @@ where
     pub tag: T,
     /// Platform in the format `os[/arch[/variant]]`
     pub platform: T,
+    /// `Dockerfile` instructions to apply to the image when importing, such as `CMD` or `ENV`.
+    /// Separate multiple instructions with a newline. This parameter may only be used when
+    /// importing an image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<T>,
+    /// Commit message for the imported image. This parameter may only be used when importing an
+    /// image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<T>,
 }
 
 /// Parameters to the [List Images
@@ -142,13 +151,13 @@ where
 {
     /// Filters to process on the prune list, encoded as JSON. Available filters:
     ///  - `dangling=<boolean>` When set to `true` (or `1`), prune only unused *and* untagged
-    ///  images. When set to `false` (or `0`), all unused images are pruned.
+    ///    images. When set to `false` (or `0`), all unused images are pruned.
     ///  - `until=<string>` Prune images created before this timestamp. The `<timestamp>` can be
-    ///  Unix timestamps, date formatted timestamps, or Go duration strings (e.g. `10m`, `1h30m`)
-    ///  computed relative to the daemon machine’s time.
+    ///    Unix timestamps, date formatted timestamps, or Go duration strings (e.g. `10m`, `1h30m`)
+    ///    computed relative to the daemon machine’s time.
     ///  - `label` (`label=<key>`, `label=<key>=<value>`, `label!=<key>`, or
-    ///  `label!=<key>=<value>`) Prune images with (or without, in case `label!=...` is used) the
-    ///  specified labels.
+    ///    `label!=<key>=<value>`) Prune images with (or without, in case `label!=...` is used) the
+    ///    specified labels.
     #[serde(serialize_with = "crate::docker::serialize_as_json")]
     pub filters: HashMap<T, Vec<T>>,
 }
@@ -424,6 +433,71 @@ pub struct ImportImageOptions {
     pub quiet: bool,
 }
 
+/// Parameters used in the [Prune Build API](Docker::prune_build())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::image::PruneBuildOptions;
+///
+/// use std::collections::HashMap;
+/// use std::default::Default;
+///
+/// let mut filters = HashMap::new();
+/// filters.insert("until", vec!["10m"]);
+///
+/// PruneBuildOptions {
+///     all: true,
+///     filters,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PruneBuildOptions<T>
+where
+    T: Into<String> + Eq + Hash + Serialize,
+{
+    /// Amount of disk space in bytes to keep for cache.
+    #[serde(rename = "keep-storage")]
+    #[serde(skip_serializing_if = "crate::container::is_zero")]
+    pub keep_storage: i64,
+    /// Remove all types of build cache.
+    pub all: bool,
+    /// Filters to process on the prune list, encoded as JSON. Available filters:
+    ///  - `until=<duration>` remove cache older than `<duration>`. The `<duration>` can be Go
+    ///    duration strings (e.g. `10m`, `1h30m`) computed relative to the daemon's local time.
+    ///  - `id=<id>`
+    ///  - `parent=<id>`
+    ///  - `type=<string>`
+    ///  - `description=<string>`
+    ///  - `inuse`
+    ///  - `shared`
+    ///  - `private`
+    #[serde(serialize_with = "crate::docker::serialize_as_json")]
+    pub filters: HashMap<T, Vec<T>>,
+}
+
+/// Result type for the [Resolve Image Digest API](Docker::resolve_image_digest())
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedImageDigest {
+    /// Image reference pinned to the digest, in the form `name@sha256:...`.
+    pub reference: String,
+    /// Digest of the manifest, or manifest list, that the tag resolves to.
+    pub digest: String,
+    /// Platforms provided by the manifest.
+    pub platforms: Vec<DistributionInspectResponsePlatforms>,
+}
+
+/// Strip the tag and digest from an image reference, leaving the repository name.
+fn image_repository(image_name: &str) -> &str {
+    let name = image_name.split('@').next().unwrap_or(image_name);
+
+    match name.rfind(':') {
+        Some(index) if !name[index..].contains('/') => &name[..index],
+        _ => name,
+    }
+}
+
 impl Docker {
     /// ---
     ///
@@ -491,11 +565,14 @@ impl Docker {
     ///  - An optional [Create Image Options](CreateImageOptions) struct.
     ///  - An optional request body consisting of a tar or tar.gz archive with the root file system
     ///    for the image. If this argument is used, the value of the `from_src` option must be "-".
+    ///  - Optional [Docker Credentials](DockerCredentials) struct, defaulting to those the client's
+    ///    [`docker_config`](crate::DockerBuilder::docker_config()) holds for the registry, if
+    ///    configured.
     ///
     /// # Returns
     ///
     ///  - [Build Info](BuildInfo), wrapped in an asynchronous
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -516,10 +593,32 @@ impl Docker {
     /// // do some other work while the image is pulled from the docker hub...
     /// ```
     ///
-    /// # Unsupported
+    /// Importing a root file system from a tarball:
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::CreateImageOptions;
+    ///
+    /// use std::default::Default;
+    /// use std::fs::File;
+    /// use std::io::Read;
     ///
-    ///  - Import from tarball
+    /// let options = Some(CreateImageOptions{
+    ///   from_src: "-",
+    ///   repo: "my-imported-image",
+    ///   tag: "latest",
+    ///   changes: Some("CMD [\"/bin/sh\"]\nENV DEBUG=1"),
+    ///   message: Some("imported from rootfs.tar"),
+    ///   ..Default::default()
+    /// });
     ///
+    /// let mut file = File::open("rootfs.tar").unwrap();
+    /// let mut contents = Vec::new();
+    /// file.read_to_end(&mut contents).unwrap();
+    ///
+    /// docker.create_image(options, Some(contents.into()), None);
+    /// ```
     pub fn create_image<T>(
         &self,
         options: Option<CreateImageOptions<T>>,
@@ -530,26 +629,32 @@ impl Docker {
         T: Into<String> + Serialize,
     {
         let url = "/images/create";
+        let image = query_field(options.as_ref(), "fromImage");
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
-            ..Default::default()
-        })) {
-            Ok(ser_cred) => {
-                let req = self.build_request(
-                    url,
-                    Builder::new()
-                        .method(Method::POST)
-                        .header("X-Registry-Auth", base64_url_encode(&ser_cred)),
-                    options,
-                    match root_fs {
-                        Some(body) => Ok(body),
-                        None => Ok(Body::empty()),
-                    },
-                );
-                self.process_into_stream(req).boxed()
-            }
-            Err(e) => stream::once(async move { Err(Error::from(e)) }).boxed(),
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            options,
+            match root_fs {
+                Some(body) => Ok(body),
+                None => Ok(Body::empty()),
+            },
+        );
+
+        let docker = self.for_silent_stream();
+        async move {
+            let credentials = docker
+                .configured_credentials(credentials, image.as_deref())
+                .await?;
+            let req = with_registry_header(
+                req,
+                "X-Registry-Auth",
+                &docker.resolve_registry_credentials(credentials, image.as_deref()),
+            );
+            Ok(docker.process_into_stream(req))
         }
+        .try_flatten_stream()
+        .boxed()
     }
 
     /// ---
@@ -653,7 +758,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - Vector of [History Response Item](HistoryResponseItem), wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -689,7 +794,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - Vector of [Image Search Response Item](ImageSearchResponseItem) results, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -745,7 +850,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - Vector of [Image Delete Response Item](ImageDeleteResponseItem), wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -771,9 +876,7 @@ impl Docker {
     ) -> Result<Vec<ImageDeleteResponseItem>, Error> {
         let url = format!("/images/{}", image_name);
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
-            ..Default::default()
-        })) {
+        match serde_json::to_string(&self.resolve_registry_credentials(credentials, None)) {
             Ok(ser_cred) => {
                 let req = self.build_request(
                     &url,
@@ -850,7 +953,9 @@ impl Docker {
     ///
     ///  - Image name as a string slice.
     ///  - Optional [Push Image Options](PushImageOptions) struct.
-    ///  - Optional [Docker Credentials](DockerCredentials) struct.
+    ///  - Optional [Docker Credentials](DockerCredentials) struct, defaulting to those the client's
+    ///    [`docker_config`](crate::DockerBuilder::docker_config()) holds for the registry, if
+    ///    configured.
     ///
     /// # Returns
     ///
@@ -889,25 +994,31 @@ impl Docker {
         T: Into<String> + Serialize,
     {
         let url = format!("/images/{}/push", image_name);
+        let image = image_name.to_string();
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
-            ..Default::default()
-        })) {
-            Ok(ser_cred) => {
-                let req = self.build_request(
-                    &url,
-                    Builder::new()
-                        .method(Method::POST)
-                        .header(CONTENT_TYPE, "application/json")
-                        .header("X-Registry-Auth", base64_url_encode(&ser_cred)),
-                    options,
-                    Ok(Body::empty()),
-                );
+        let req = self.build_request(
+            &url,
+            Builder::new()
+                .method(Method::POST)
+                .header(CONTENT_TYPE, "application/json"),
+            options,
+            Ok(Body::empty()),
+        );
 
-                self.process_into_stream(req).boxed()
-            }
-            Err(e) => stream::once(async move { Err(e.into()) }).boxed(),
+        let docker = self.for_silent_stream();
+        async move {
+            let credentials = docker
+                .configured_credentials(credentials, Some(&image))
+                .await?;
+            let req = with_registry_header(
+                req,
+                "X-Registry-Auth",
+                &docker.resolve_registry_credentials(credentials, Some(&image)),
+            );
+            Ok(docker.process_into_stream(req))
         }
+        .try_flatten_stream()
+        .boxed()
     }
 
     /// ---
@@ -981,14 +1092,16 @@ impl Docker {
     /// # Arguments
     ///
     ///  - [Build Image Options](BuildImageOptions) struct.
-    ///  - Optional [Docker Credentials](DockerCredentials) struct.
+    ///  - Optional map of [Docker Credentials](DockerCredentials) by registry server address,
+    ///    defaulting to every credential of the client's
+    ///    [`docker_config`](crate::DockerBuilder::docker_config()), if configured.
     ///  - Tar archive compressed with one of the following algorithms: identity (no compression),
     ///    gzip, bzip2, xz. Optional [Hyper Body](hyper::body::Body).
     ///
     /// # Returns
     ///
     ///  - [Create Image Info](CreateImageInfo), wrapped in an asynchronous
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -1026,22 +1139,80 @@ impl Docker {
     {
         let url = "/build";
 
-        match serde_json::to_string(&credentials.unwrap_or_else(HashMap::new)) {
-            Ok(ser_cred) => {
-                let req = self.build_request(
-                    url,
-                    Builder::new()
-                        .method(Method::POST)
-                        .header(CONTENT_TYPE, "application/x-tar")
-                        .header("X-Registry-Config", base64_url_encode(&ser_cred)),
-                    Some(options),
-                    Ok(tar.unwrap_or_else(Body::empty)),
-                );
+        let req = self.build_request(
+            url,
+            Builder::new()
+                .method(Method::POST)
+                .header(CONTENT_TYPE, "application/x-tar"),
+            Some(options),
+            Ok(tar.unwrap_or_else(Body::empty)),
+        );
 
-                self.process_into_stream(req).boxed()
-            }
-            Err(e) => stream::once(async move { Err(e.into()) }).boxed(),
+        let docker = self.for_silent_stream();
+        async move {
+            let credentials = match (credentials, &docker.docker_config) {
+                (None, Some(config)) => config.all_credentials().await?,
+                (credentials, _) => credentials.unwrap_or_default(),
+            };
+            let req = with_registry_header(req, "X-Registry-Config", &credentials);
+            Ok(docker.process_into_stream(req))
         }
+        .try_flatten_stream()
+        .boxed()
+    }
+
+    /// ---
+    ///
+    /// # Prune Build
+    ///
+    /// Delete builder cache.
+    ///
+    /// # Arguments
+    ///
+    ///  - An optional [Prune Build Options](PruneBuildOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - a [Prune Build Response](BuildPruneResponse), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::PruneBuildOptions;
+    ///
+    /// use std::collections::HashMap;
+    /// use std::default::Default;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("until", vec!["10m"]);
+    ///
+    /// let options = Some(PruneBuildOptions {
+    ///     keep_storage: 512 * 1024 * 1024,
+    ///     filters,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// docker.prune_build(options);
+    /// ```
+    pub async fn prune_build<T>(
+        &self,
+        options: Option<PruneBuildOptions<T>>,
+    ) -> Result<BuildPruneResponse, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let url = "/build/prune";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            options,
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
     }
 
     /// ---
@@ -1077,6 +1248,49 @@ impl Docker {
         self.process_into_body(req)
     }
 
+    /// ---
+    ///
+    /// # Export Images
+    ///
+    /// Get a tarball containing all images and metadata for several image repositories. Layers
+    /// shared between the images are only included once.
+    ///
+    /// For details on the format, see the [export image
+    /// endpoint](struct.Docker.html#method.export_image).
+    ///
+    /// # Arguments
+    ///
+    ///  - A slice of image names, each referring to an individual image and tag (e.g.
+    ///    `alpine:latest`), an individual image by ID, or an image repository.
+    ///
+    /// # Returns
+    ///
+    ///  - An uncompressed TAR archive, wrapped in a Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.export_images(&["alpine:latest", "hello-world:linux"]);
+    /// ```
+    pub fn export_images(&self, image_names: &[&str]) -> impl Stream<Item = Result<Bytes, Error>> {
+        let url = "/images/get";
+
+        let query: Vec<_> = image_names.iter().map(|name| ("names", name)).collect();
+
+        let req = self.build_request(
+            url,
+            Builder::new()
+                .method(Method::GET)
+                .header(CONTENT_TYPE, "application/json"),
+            Some(query),
+            Ok(Body::empty()),
+        );
+        self.process_into_body(req)
+    }
+
     /// ---
     ///
     /// # Import Image
@@ -1092,7 +1306,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [Build Info](BuildInfo), wrapped in an asynchronous
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -1119,9 +1333,9 @@ impl Docker {
     ///         let bytes = r.unwrap().freeze();
     ///         Ok::<_, Error>(bytes)
     ///     });
-
+    ///
     ///     let body = hyper::Body::wrap_stream(byte_stream);
-
+    ///
     ///     let mut stream = docker
     ///         .import_image(
     ///             ImportImageOptions {
@@ -1142,7 +1356,7 @@ impl Docker {
         root_fs: Body,
         credentials: Option<HashMap<String, DockerCredentials>>,
     ) -> impl Stream<Item = Result<BuildInfo, Error>> {
-        match serde_json::to_string(&credentials.unwrap_or_else(HashMap::new)) {
+        match serde_json::to_string(&credentials.unwrap_or_default()) {
             Ok(ser_cred) => {
                 let req = self.build_request(
                     "/images/load",
@@ -1153,9 +1367,128 @@ impl Docker {
                     Some(options),
                     Ok(root_fs),
                 );
-                self.process_into_stream(req).boxed()
+                self.for_silent_stream().process_into_stream(req).boxed()
             }
             Err(e) => stream::once(async move { Err(e.into()) }).boxed(),
         }
     }
+
+    /// ---
+    ///
+    /// # Inspect Registry Image
+    ///
+    /// Return image digest and platform information by contacting the registry, without pulling
+    /// the image.
+    ///
+    /// # Arguments
+    ///
+    ///  - Image name as a string slice.
+    ///  - Optional [Docker Credentials](DockerCredentials) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Distribution Inspect Response](DistributionInspectResponse), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.inspect_registry_image("alpine:3.14", None);
+    /// ```
+    pub async fn inspect_registry_image(
+        &self,
+        image_name: &str,
+        credentials: Option<DockerCredentials>,
+    ) -> Result<DistributionInspectResponse, Error> {
+        let url = format!("/distribution/{}/json", image_name);
+
+        let ser_cred = serde_json::to_string(
+            &self.resolve_registry_credentials(credentials, Some(image_name)),
+        )?;
+
+        let req = self.build_request(
+            &url,
+            Builder::new()
+                .method(Method::GET)
+                .header("X-Registry-Auth", base64_url_encode(&ser_cred)),
+            None::<String>,
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Resolve Image Digest
+    ///
+    /// Resolve a tagged image reference to a reference pinned by digest, by contacting the
+    /// registry with the [Inspect Registry Image API](Docker::inspect_registry_image()).
+    ///
+    /// # Arguments
+    ///
+    ///  - Image name as a string slice.
+    ///  - Optional [Docker Credentials](DockerCredentials) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Resolved Image Digest](ResolvedImageDigest), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// async move {
+    ///     let resolved = docker.resolve_image_digest("alpine:3.14", None).await?;
+    ///
+    ///     // e.g. alpine@sha256:...
+    ///     println!("{}", resolved.reference);
+    ///     # Ok::<_, bollard::errors::Error>(())
+    /// };
+    /// ```
+    pub async fn resolve_image_digest(
+        &self,
+        image_name: &str,
+        credentials: Option<DockerCredentials>,
+    ) -> Result<ResolvedImageDigest, Error> {
+        let response = self.inspect_registry_image(image_name, credentials).await?;
+
+        let digest =
+            response
+                .descriptor
+                .digest
+                .ok_or_else(|| Error::RegistryDigestMissingError {
+                    image: String::from(image_name),
+                })?;
+
+        Ok(ResolvedImageDigest {
+            reference: format!("{}@{}", image_repository(image_name), digest),
+            digest,
+            platforms: response.platforms,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::image_repository;
+
+    #[test]
+    fn test_image_repository() {
+        assert_eq!(image_repository("alpine"), "alpine");
+        assert_eq!(image_repository("alpine:3.14"), "alpine");
+        assert_eq!(
+            image_repository("localhost:5000/my/image:prod"),
+            "localhost:5000/my/image"
+        );
+        assert_eq!(
+            image_repository("localhost:5000/my/image"),
+            "localhost:5000/my/image"
+        );
+        assert_eq!(image_repository("alpine:3.14@sha256:abcdef"), "alpine");
+    }
 }
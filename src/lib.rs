@@ -91,6 +91,10 @@
 //! certificates - `key.pem` for the private key, `cert.pem` for the server certificate and
 //! `ca.pem` for the certificate authority chain.
 //!
+//! The `DockerBuilder` and `Docker::connect_with_defaults` also connect to a `tcp://` url over
+//! TLS when the `DOCKER_TLS_VERIFY` environment variable is set, using the certificates in
+//! `DOCKER_CERT_PATH`, and fail to connect if the `ssl` feature is disabled.
+//!
 //! Use the `Docker::connect_with_ssl` method API
 //! to parameterise the interface.
 //!
@@ -100,6 +104,32 @@
 //! Docker::connect_with_ssl_defaults();
 //! ```
 //!
+//! ### Context
+//!
+//! The client will connect to the daemon selected with `docker context use`, unless overridden
+//! the same way as for the docker CLI: the `DOCKER_HOST` environment variable takes precedence
+//! over the `DOCKER_CONTEXT` environment variable, which takes precedence over the current
+//! context. Use the `Docker::connect_with_context` method API to connect to a named context.
+//!
+//! ```rust,no_run
+//! use bollard::Docker;
+//! Docker::connect_with_defaults();
+//! ```
+//!
+//! ### Builder
+//!
+//! The `DockerBuilder` picks the transport from the scheme of the connection url (`unix://`,
+//! `npipe://`, `tcp://`, `http://`, `https://` or `ssh://`), and configures the timeout, client
+//! version, default headers, user agent, connection pool and certificates.
+//!
+//! ```rust
+//! use bollard::DockerBuilder;
+//! DockerBuilder::new()
+//!     .url("http://localhost:2375")
+//!     .user_agent("my-app/1.0")
+//!     .build();
+//! ```
+//!
 //! ## Examples
 //!
 //! Note: all these examples need a [Tokio
@@ -247,19 +277,34 @@ extern crate log;
 
 // declare modules
 pub mod auth;
+pub mod cassette;
+pub mod config;
 pub mod container;
+mod context;
 mod docker;
 pub mod errors;
 pub mod exec;
 pub mod image;
 mod named_pipe;
 pub mod network;
+pub mod node;
+pub mod plugin;
 mod read;
+mod retry;
+pub mod secret;
 pub mod service;
+mod ssh;
+pub mod swarm;
 pub mod system;
+pub mod task;
+#[cfg(all(unix, feature = "test-server"))]
+pub mod test_server;
+pub mod transport;
 mod uri;
 pub mod volume;
+mod websocket;
 
 // publicly re-export
-pub use crate::docker::{ClientVersion, Docker, API_DEFAULT_VERSION};
+pub use crate::docker::{ClientVersion, Docker, DockerBuilder, API_DEFAULT_VERSION};
+pub use crate::retry::RetryPolicy;
 pub use bollard_stubs::models;
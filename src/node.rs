@@ -0,0 +1,273 @@
+//! Node API: Nodes are instances of the Engine participating in a swarm. Swarm mode must be
+//! enabled for these endpoints to work.
+
+pub use crate::models::*;
+
+use super::Docker;
+use crate::errors::Error;
+use http::request::Builder;
+use hyper::{body::Body as HyperBody, Method};
+use serde::ser::Serialize;
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Parameters used in the [List Nodes API](super::Docker::list_nodes())
+///
+/// ## Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// use bollard::node::ListNodesOptions;
+///
+/// let mut filters = HashMap::new();
+/// filters.insert("role", vec!["worker"]);
+///
+/// ListNodesOptions{
+///     filters,
+/// };
+/// ```
+///
+/// ```rust
+/// # use bollard::node::ListNodesOptions;
+/// # use std::default::Default;
+///
+/// let options: ListNodesOptions<&str> = Default::default();
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListNodesOptions<T>
+where
+    T: Into<String> + Eq + Hash + Serialize,
+{
+    /// Filters to process on the nodes list, encoded as JSON. Available filters:
+    ///  - `id`=`<node id>` a node's ID
+    ///  - `label`=`key` or `label`=`"key=value"` of a node label
+    ///  - `membership`=(`accepted`|`pending`)
+    ///  - `name`=`<node name>` a node's name
+    ///  - `node.label`=`key` or `node.label`=`"key=value"` of a node label
+    ///  - `role`=(`manager`|`worker`)
+    #[serde(serialize_with = "crate::docker::serialize_as_json")]
+    pub filters: HashMap<T, Vec<T>>,
+}
+
+/// Parameters used in the [Update Node API](Docker::update_node())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::node::UpdateNodeOptions;
+///
+/// UpdateNodeOptions{
+///     version: 1234,
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, Serialize)]
+pub struct UpdateNodeOptions {
+    /// The version number of the node object being updated. This is required to avoid conflicting
+    /// writes.
+    pub version: u64,
+}
+
+/// Parameters used in the [Delete Node API](Docker::delete_node())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::node::DeleteNodeOptions;
+///
+/// DeleteNodeOptions{
+///     force: true,
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, Serialize)]
+pub struct DeleteNodeOptions {
+    /// Force remove a node from the swarm.
+    pub force: bool,
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # List Nodes
+    ///
+    /// Returns a list of nodes participating in the swarm.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [ListNodesOptions](ListNodesOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Nodes](Node), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::node::ListNodesOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("role", vec!["worker"]);
+    ///
+    /// let options = Some(ListNodesOptions{
+    ///     filters,
+    /// });
+    ///
+    /// docker.list_nodes(options);
+    /// ```
+    pub async fn list_nodes<T>(
+        &self,
+        options: Option<ListNodesOptions<T>>,
+    ) -> Result<Vec<Node>, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let url = "/nodes";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Inspect Node
+    ///
+    /// Inspect a node.
+    ///
+    /// # Arguments
+    ///
+    ///  - Node id or name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - [Node](Node), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.inspect_node("my-node");
+    /// ```
+    pub async fn inspect_node(&self, node_name: &str) -> Result<Node, Error> {
+        let url = format!("/nodes/{}", node_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Update Node
+    ///
+    /// Update a node's spec, for example to change its availability or role.
+    ///
+    /// # Arguments
+    ///
+    ///  - Node id or name as a string slice.
+    ///  - [NodeSpec](NodeSpec) struct.
+    ///  - [UpdateNodeOptions](UpdateNodeOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::node::{NodeSpec, NodeSpecAvailabilityEnum, UpdateNodeOptions};
+    ///
+    /// let result = async move {
+    ///     let node_name = "my-node";
+    ///     let node = docker.inspect_node(node_name).await?;
+    ///     let spec = NodeSpec {
+    ///         availability: Some(NodeSpecAvailabilityEnum::DRAIN),
+    ///         ..node.spec.unwrap()
+    ///     };
+    ///     let options = UpdateNodeOptions {
+    ///         version: node.version.unwrap().index.unwrap(),
+    ///     };
+    ///
+    ///     docker.update_node(node_name, spec, options).await
+    /// };
+    /// ```
+    pub async fn update_node(
+        &self,
+        node_name: &str,
+        spec: NodeSpec,
+        options: UpdateNodeOptions,
+    ) -> Result<(), Error> {
+        let url = format!("/nodes/{}/update", node_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::POST),
+            Some(options),
+            Docker::serialize_payload(Some(spec)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Delete Node
+    ///
+    /// Delete a node from the swarm.
+    ///
+    /// # Arguments
+    ///
+    ///  - Node id or name as a string slice.
+    ///  - Optional [DeleteNodeOptions](DeleteNodeOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::node::DeleteNodeOptions;
+    ///
+    /// let options = Some(DeleteNodeOptions{
+    ///     force: true,
+    /// });
+    ///
+    /// docker.delete_node("my-node", options);
+    /// ```
+    pub async fn delete_node(
+        &self,
+        node_name: &str,
+        options: Option<DeleteNodeOptions>,
+    ) -> Result<(), Error> {
+        let url = format!("/nodes/{}", node_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::DELETE),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_unit(req).await
+    }
+}
@@ -0,0 +1,734 @@
+//! Plugin API: Install, configure and manage docker engine plugins
+
+pub use crate::models::*;
+
+use futures_core::Stream;
+use futures_util::{stream, stream::StreamExt};
+use http::header::CONTENT_TYPE;
+use http::request::Builder;
+use hyper::{body::Body as HyperBody, Method};
+use serde::Serialize;
+
+use super::Docker;
+use crate::auth::{base64_url_encode, query_field, DockerCredentials};
+use crate::errors::Error;
+
+use std::cmp::Eq;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Parameters used in the [List Plugins API](Docker::list_plugins())
+///
+/// ## Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// use bollard::plugin::ListPluginsOptions;
+///
+/// let mut filters = HashMap::new();
+/// filters.insert("capability", vec!["volumedriver"]);
+///
+/// ListPluginsOptions{
+///     filters,
+/// };
+/// ```
+///
+/// ```rust
+/// # use bollard::plugin::ListPluginsOptions;
+/// # use std::default::Default;
+///
+/// let options: ListPluginsOptions<&str> = Default::default();
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListPluginsOptions<T>
+where
+    T: Into<String> + Eq + Hash + Serialize,
+{
+    /// Filters to process on the plugin list, encoded as JSON. Available filters:
+    ///  - `capability`=`<capability name>`
+    ///  - `enable`=(`true`|`false`)
+    #[serde(serialize_with = "crate::docker::serialize_as_json")]
+    pub filters: HashMap<T, Vec<T>>,
+}
+
+/// Parameters used in the [Install Plugin API](Docker::install_plugin())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::plugin::InstallPluginOptions;
+///
+/// InstallPluginOptions{
+///     remote: "vieux/sshfs:latest",
+///     name: Some("sshfs"),
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InstallPluginOptions<T>
+where
+    T: Into<String> + Serialize,
+{
+    /// Remote reference for the plugin to install. The `:latest` tag is optional, and is used as
+    /// the default if omitted.
+    pub remote: T,
+    /// Local name for the pulled plugin. Defaults to the remote reference if omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<T>,
+}
+
+/// Parameters used in the [Enable Plugin API](Docker::enable_plugin())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::plugin::EnablePluginOptions;
+///
+/// EnablePluginOptions{
+///     timeout: 30,
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, Serialize)]
+pub struct EnablePluginOptions {
+    /// Set the HTTP client timeout (in seconds).
+    pub timeout: i64,
+}
+
+/// Parameters used in the [Disable Plugin API](Docker::disable_plugin())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::plugin::DisablePluginOptions;
+///
+/// DisablePluginOptions{
+///     force: true,
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, Serialize)]
+pub struct DisablePluginOptions {
+    /// Force disable a plugin even if still in use.
+    pub force: bool,
+}
+
+/// Parameters used in the [Upgrade Plugin API](Docker::upgrade_plugin())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::plugin::UpgradePluginOptions;
+///
+/// UpgradePluginOptions{
+///     remote: "vieux/sshfs:next",
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpgradePluginOptions<T>
+where
+    T: Into<String> + Serialize,
+{
+    /// Remote reference to upgrade to. The `:latest` tag is optional, and is used as the default
+    /// if omitted.
+    pub remote: T,
+}
+
+/// Parameters used in the [Remove Plugin API](Docker::remove_plugin())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::plugin::RemovePluginOptions;
+///
+/// RemovePluginOptions{
+///     force: true,
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, Serialize)]
+pub struct RemovePluginOptions {
+    /// Disable the plugin before removing. This may result in issues if the plugin is in use by a
+    /// container.
+    pub force: bool,
+}
+
+/// Parameters used in the [Create Plugin API](Docker::create_plugin())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::plugin::CreatePluginOptions;
+///
+/// CreatePluginOptions{
+///     name: "my-plugin:latest",
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreatePluginOptions<T>
+where
+    T: Into<String> + Serialize,
+{
+    /// The name of the plugin. The `:latest` tag is optional, and is the default if omitted.
+    pub name: T,
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # List Plugins
+    ///
+    /// Returns information about installed plugins.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [List Plugins Options](ListPluginsOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Plugin](Plugin), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::plugin::ListPluginsOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("enable", vec!["true"]);
+    ///
+    /// let options = Some(ListPluginsOptions{
+    ///     filters,
+    /// });
+    ///
+    /// docker.list_plugins(options);
+    /// ```
+    pub async fn list_plugins<T>(
+        &self,
+        options: Option<ListPluginsOptions<T>>,
+    ) -> Result<Vec<Plugin>, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let url = "/plugins";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Get Plugin Privileges
+    ///
+    /// Get the privileges a plugin requires before it can be installed.
+    ///
+    /// # Arguments
+    ///
+    ///  - Remote reference of the plugin as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Plugin Privilege Item](PluginPrivilegeItem), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.get_plugin_privileges("vieux/sshfs:latest");
+    /// ```
+    pub async fn get_plugin_privileges(
+        &self,
+        remote: &str,
+    ) -> Result<Vec<PluginPrivilegeItem>, Error> {
+        let url = "/plugins/privileges";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            Some([("remote", remote)]),
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Install Plugin
+    ///
+    /// Pull and install a plugin. After the plugin is installed, it can be enabled using the
+    /// [Enable Plugin API](Docker::enable_plugin()).
+    ///
+    /// # Arguments
+    ///
+    ///  - [Install Plugin Options](InstallPluginOptions) struct.
+    ///  - Vector of [Plugin Privilege Item](PluginPrivilegeItem) granted to the plugin, usually
+    ///    the result of the [Get Plugin Privileges API](Docker::get_plugin_privileges()).
+    ///  - Optional [Docker Credentials](DockerCredentials) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Create Image Info](CreateImageInfo), wrapped in an asynchronous
+    ///    Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::plugin::InstallPluginOptions;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// async move {
+    ///     let privileges = docker.get_plugin_privileges("vieux/sshfs").await?;
+    ///
+    ///     let options = InstallPluginOptions {
+    ///         remote: "vieux/sshfs",
+    ///         name: Some("sshfs"),
+    ///     };
+    ///
+    ///     docker
+    ///         .install_plugin(options, privileges, None)
+    ///         .try_collect::<Vec<_>>()
+    ///         .await
+    /// };
+    /// ```
+    pub fn install_plugin<T>(
+        &self,
+        options: InstallPluginOptions<T>,
+        privileges: Vec<PluginPrivilegeItem>,
+        credentials: Option<DockerCredentials>,
+    ) -> impl Stream<Item = Result<CreateImageInfo, Error>>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = "/plugins/pull";
+        let remote = query_field(Some(&options), "remote");
+
+        match serde_json::to_string(
+            &self.resolve_registry_credentials(credentials, remote.as_deref()),
+        ) {
+            Ok(ser_cred) => {
+                let req = self.build_request(
+                    url,
+                    Builder::new()
+                        .method(Method::POST)
+                        .header("X-Registry-Auth", base64_url_encode(&ser_cred)),
+                    Some(options),
+                    Docker::serialize_payload(Some(privileges)),
+                );
+
+                self.for_silent_stream().process_into_stream(req).boxed()
+            }
+            Err(e) => stream::once(async move { Err(e.into()) }).boxed(),
+        }
+    }
+
+    /// ---
+    ///
+    /// # Inspect Plugin
+    ///
+    /// Return low-level information about a plugin.
+    ///
+    /// # Arguments
+    ///
+    ///  - Plugin name as a string slice. The `:latest` tag is optional.
+    ///
+    /// # Returns
+    ///
+    ///  - [Plugin](Plugin), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.inspect_plugin("sshfs");
+    /// ```
+    pub async fn inspect_plugin(&self, plugin_name: &str) -> Result<Plugin, Error> {
+        let url = format!("/plugins/{}/json", plugin_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Enable Plugin
+    ///
+    /// Enable an installed plugin.
+    ///
+    /// # Arguments
+    ///
+    ///  - Plugin name as a string slice.
+    ///  - Optional [Enable Plugin Options](EnablePluginOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::plugin::EnablePluginOptions;
+    ///
+    /// let options = Some(EnablePluginOptions{
+    ///     timeout: 30,
+    /// });
+    ///
+    /// docker.enable_plugin("sshfs", options);
+    /// ```
+    pub async fn enable_plugin(
+        &self,
+        plugin_name: &str,
+        options: Option<EnablePluginOptions>,
+    ) -> Result<(), Error> {
+        let url = format!("/plugins/{}/enable", plugin_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::POST),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Disable Plugin
+    ///
+    /// Disable a running plugin.
+    ///
+    /// # Arguments
+    ///
+    ///  - Plugin name as a string slice.
+    ///  - Optional [Disable Plugin Options](DisablePluginOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::plugin::DisablePluginOptions;
+    ///
+    /// let options = Some(DisablePluginOptions{
+    ///     force: true,
+    /// });
+    ///
+    /// docker.disable_plugin("sshfs", options);
+    /// ```
+    pub async fn disable_plugin(
+        &self,
+        plugin_name: &str,
+        options: Option<DisablePluginOptions>,
+    ) -> Result<(), Error> {
+        let url = format!("/plugins/{}/disable", plugin_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::POST),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Upgrade Plugin
+    ///
+    /// Upgrade a disabled plugin to a new remote reference.
+    ///
+    /// # Arguments
+    ///
+    ///  - Plugin name as a string slice.
+    ///  - [Upgrade Plugin Options](UpgradePluginOptions) struct.
+    ///  - Vector of [Plugin Privilege Item](PluginPrivilegeItem) granted to the plugin.
+    ///  - Optional [Docker Credentials](DockerCredentials) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Create Image Info](CreateImageInfo), wrapped in an asynchronous
+    ///    Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::plugin::UpgradePluginOptions;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// async move {
+    ///     let privileges = docker.get_plugin_privileges("vieux/sshfs:next").await?;
+    ///
+    ///     let options = UpgradePluginOptions {
+    ///         remote: "vieux/sshfs:next",
+    ///     };
+    ///
+    ///     docker
+    ///         .upgrade_plugin("sshfs", options, privileges, None)
+    ///         .try_collect::<Vec<_>>()
+    ///         .await
+    /// };
+    /// ```
+    pub fn upgrade_plugin<T>(
+        &self,
+        plugin_name: &str,
+        options: UpgradePluginOptions<T>,
+        privileges: Vec<PluginPrivilegeItem>,
+        credentials: Option<DockerCredentials>,
+    ) -> impl Stream<Item = Result<CreateImageInfo, Error>>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = format!("/plugins/{}/upgrade", plugin_name);
+        let remote = query_field(Some(&options), "remote");
+
+        match serde_json::to_string(
+            &self.resolve_registry_credentials(credentials, remote.as_deref()),
+        ) {
+            Ok(ser_cred) => {
+                let req = self.build_request(
+                    &url,
+                    Builder::new()
+                        .method(Method::POST)
+                        .header("X-Registry-Auth", base64_url_encode(&ser_cred)),
+                    Some(options),
+                    Docker::serialize_payload(Some(privileges)),
+                );
+
+                self.for_silent_stream().process_into_stream(req).boxed()
+            }
+            Err(e) => stream::once(async move { Err(e.into()) }).boxed(),
+        }
+    }
+
+    /// ---
+    ///
+    /// # Set Plugin
+    ///
+    /// Configure a plugin, by setting its environment variables, mount sources, device paths or
+    /// arguments.
+    ///
+    /// # Arguments
+    ///
+    ///  - Plugin name as a string slice.
+    ///  - Vector of settings in the form `KEY=value`, or `name.source=value` for mounts and
+    ///    devices.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.set_plugin("sshfs", vec!["DEBUG=1"]);
+    /// ```
+    pub async fn set_plugin<T>(&self, plugin_name: &str, settings: Vec<T>) -> Result<(), Error>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = format!("/plugins/{}/set", plugin_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::POST),
+            None::<String>,
+            Docker::serialize_payload(Some(settings)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Remove Plugin
+    ///
+    /// Remove an installed plugin.
+    ///
+    /// # Arguments
+    ///
+    ///  - Plugin name as a string slice.
+    ///  - Optional [Remove Plugin Options](RemovePluginOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Plugin](Plugin) that was removed, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::plugin::RemovePluginOptions;
+    ///
+    /// let options = Some(RemovePluginOptions{
+    ///     force: true,
+    /// });
+    ///
+    /// docker.remove_plugin("sshfs", options);
+    /// ```
+    pub async fn remove_plugin(
+        &self,
+        plugin_name: &str,
+        options: Option<RemovePluginOptions>,
+    ) -> Result<Plugin, Error> {
+        let url = format!("/plugins/{}", plugin_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::DELETE),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Create Plugin
+    ///
+    /// Create a plugin from a tar archive. The archive must contain a `config.json` file with the
+    /// plugin configuration, and a `rootfs` directory with the plugin's root file system.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Create Plugin Options](CreatePluginOptions) struct.
+    ///  - Tar archive of the plugin context, as a request body.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::plugin::CreatePluginOptions;
+    ///
+    /// use std::fs::File;
+    /// use std::io::Read;
+    ///
+    /// let options = CreatePluginOptions{
+    ///     name: "my-plugin",
+    /// };
+    ///
+    /// let mut file = File::open("plugin.tar").unwrap();
+    /// let mut contents = Vec::new();
+    /// file.read_to_end(&mut contents).unwrap();
+    ///
+    /// docker.create_plugin(options, contents.into());
+    /// ```
+    pub async fn create_plugin<T>(
+        &self,
+        options: CreatePluginOptions<T>,
+        tar: HyperBody,
+    ) -> Result<(), Error>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = "/plugins/create";
+
+        let req = self.build_request(
+            url,
+            Builder::new()
+                .method(Method::POST)
+                .header(CONTENT_TYPE, "application/x-tar"),
+            Some(options),
+            Ok(tar),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Push Plugin
+    ///
+    /// Push a plugin to the registry.
+    ///
+    /// # Arguments
+    ///
+    ///  - Plugin name as a string slice.
+    ///  - Optional [Docker Credentials](DockerCredentials) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Push Image Info](PushImageInfo), wrapped in an asynchronous
+    ///    Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::auth::DockerCredentials;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let credentials = Some(DockerCredentials {
+    ///     username: Some("Jack".to_string()),
+    ///     password: Some("myverysecretpassword".to_string()),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// docker.push_plugin("my-plugin", credentials);
+    /// ```
+    pub fn push_plugin(
+        &self,
+        plugin_name: &str,
+        credentials: Option<DockerCredentials>,
+    ) -> impl Stream<Item = Result<PushImageInfo, Error>> {
+        let url = format!("/plugins/{}/push", plugin_name);
+
+        match serde_json::to_string(
+            &self.resolve_registry_credentials(credentials, Some(plugin_name)),
+        ) {
+            Ok(ser_cred) => {
+                let req = self.build_request(
+                    &url,
+                    Builder::new()
+                        .method(Method::POST)
+                        .header("X-Registry-Auth", base64_url_encode(&ser_cred)),
+                    None::<String>,
+                    Ok(HyperBody::empty()),
+                );
+
+                self.for_silent_stream().process_into_stream(req).boxed()
+            }
+            Err(e) => stream::once(async move { Err(e.into()) }).boxed(),
+        }
+    }
+}
@@ -105,7 +105,7 @@ impl<T> JsonLineDecoder<T> {
 fn decode_json_from_slice<T: DeserializeOwned>(slice: &[u8]) -> Result<Option<T>, Error> {
     debug!(
         "Decoding JSON line from stream: {}",
-        String::from_utf8_lossy(slice).to_string()
+        String::from_utf8_lossy(slice)
     );
 
     match serde_json::from_slice(slice) {
@@ -214,6 +214,7 @@ where
                     Poll::Pending => {
                         return Poll::Pending;
                     }
+                    #[allow(clippy::io_other_error)]
                     Poll::Ready(Some(Err(e))) => {
                         return Poll::Ready(Err(io::Error::new(
                             io::ErrorKind::Other,
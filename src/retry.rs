@@ -0,0 +1,391 @@
+//! Retry policy: resend requests that failed with a transient error, such as a refused connection
+//! while the daemon restarts.
+
+use hyper::body::HttpBody;
+use hyper::{Body, Method, Request, StatusCode};
+use rand::Rng;
+
+use std::error::Error as StdError;
+use std::io::{self, ErrorKind};
+use std::iter;
+use std::time::Duration;
+
+use crate::errors::Error;
+use crate::errors::Error::*;
+
+/// Request bodies up to this length are buffered so that they can be sent again. Longer bodies,
+/// and streamed bodies of unknown length, are sent once.
+const MAX_BUFFERED_BODY_LENGTH: u64 = 1024 * 1024;
+
+/// ---
+///
+/// # RetryPolicy
+///
+/// How often and when a request is sent again after a transient failure, set on the client with
+/// [`DockerBuilder::retry_policy`] or [`Docker::with_retry_policy`].
+///
+/// Only requests with an idempotent method are retried, which are `GET` and `HEAD` unless other
+/// methods are opted in with [`RetryPolicy::retry_method`]. The request body of an opted in
+/// method is buffered in memory, so that it can be sent again, if it is known to be at most 1 MiB
+/// long. Requests with longer or streamed bodies, such as tar uploads, are sent once.
+///
+/// A request is retried until the response headers are received, so a streamed response is never
+/// retried once data has been yielded from it.
+///
+/// # Defaults
+///
+///  - Requests are sent at most 3 times.
+///  - The delay before a retry starts at 100 milliseconds and doubles after every attempt, up to
+///    5 seconds. A random jitter of up to half the delay is subtracted.
+///  - Responses with status code `502`, `503` or `504` are retried.
+///  - Connection errors are retried, timeouts are not.
+///
+/// # Examples
+///
+/// ```rust
+/// use bollard::{DockerBuilder, RetryPolicy};
+///
+/// use hyper::Method;
+///
+/// use std::time::Duration;
+///
+/// let docker = DockerBuilder::new()
+///     .url("tcp://localhost:2375")
+///     .retry_policy(
+///         RetryPolicy::new()
+///             .max_attempts(5)
+///             .initial_backoff(Duration::from_millis(250))
+///             .retry_timeouts(true),
+///     )
+///     .build()
+///     .unwrap();
+///
+/// // Also retry removing a container, on this call only.
+/// async move {
+///     docker
+///         .with_retry_policy(RetryPolicy::new().retry_method(Method::DELETE))
+///         .remove_container("my-container", None)
+///         .await
+/// };
+/// ```
+///
+/// [`DockerBuilder::retry_policy`]: crate::DockerBuilder::retry_policy()
+/// [`Docker::with_retry_policy`]: crate::Docker::with_retry_policy()
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: u32,
+    jitter: bool,
+    methods: Vec<Method>,
+    status_codes: Vec<u16>,
+    retry_connection_errors: bool,
+    retry_timeouts: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2,
+            jitter: true,
+            methods: vec![Method::GET, Method::HEAD],
+            status_codes: vec![502, 503, 504],
+            retry_connection_errors: true,
+            retry_timeouts: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy with the default settings.
+    pub fn new() -> RetryPolicy {
+        RetryPolicy::default()
+    }
+
+    /// Set the number of times a request is sent, including the first attempt. A value of `1`
+    /// disables retries.
+    pub fn max_attempts(mut self, max_attempts: u32) -> RetryPolicy {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry.
+    pub fn initial_backoff(mut self, backoff: Duration) -> RetryPolicy {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the upper bound of the delay between two attempts.
+    pub fn max_backoff(mut self, backoff: Duration) -> RetryPolicy {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Set the factor the delay is multiplied with after every attempt.
+    pub fn multiplier(mut self, multiplier: u32) -> RetryPolicy {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Set whether a random jitter is subtracted from the delay, so that clients failing at the
+    /// same time do not retry at the same time.
+    pub fn jitter(mut self, jitter: bool) -> RetryPolicy {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Opt requests with this method in to retries, on top of `GET` and `HEAD`. Only opt in
+    /// methods of calls that are safe to repeat.
+    pub fn retry_method(mut self, method: Method) -> RetryPolicy {
+        if !self.methods.contains(&method) {
+            self.methods.push(method);
+        }
+        self
+    }
+
+    /// Set the response status codes that are retried.
+    pub fn retryable_status_codes(mut self, status_codes: &[u16]) -> RetryPolicy {
+        self.status_codes = status_codes.to_vec();
+        self
+    }
+
+    /// Set whether requests are retried when the connection to the daemon is refused, reset or
+    /// aborted, or is closed before the response headers are received. Other errors, such as a
+    /// missing socket, a denied permission or a failed TLS handshake, are never retried.
+    pub fn retry_connection_errors(mut self, retry: bool) -> RetryPolicy {
+        self.retry_connection_errors = retry;
+        self
+    }
+
+    /// Set whether requests are retried when the response headers are not received within the
+    /// client timeout.
+    pub fn retry_timeouts(mut self, retry: bool) -> RetryPolicy {
+        self.retry_timeouts = retry;
+        self
+    }
+
+    pub(crate) fn applies_to(&self, request: &Request<Body>) -> bool {
+        self.max_attempts > 1
+            && self.methods.contains(request.method())
+            && matches!(
+                request.body().size_hint().exact(),
+                Some(length) if length <= MAX_BUFFERED_BODY_LENGTH
+            )
+    }
+
+    pub(crate) fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.status_codes.contains(&status.as_u16())
+    }
+
+    pub(crate) fn is_retryable_error(&self, err: &Error) -> bool {
+        match err {
+            // A connection closed before the response carries no I/O error of its own.
+            HyperResponseError { err } if err.is_connect() || err.is_incomplete_message() => {
+                self.retry_connection_errors
+                    && io_error_kind(err).map_or(err.is_incomplete_message(), is_connection_error)
+            }
+            IOError { err } => self.retry_connection_errors && is_connection_error(err.kind()),
+            RequestTimeoutError => self.retry_timeouts,
+            _ => false,
+        }
+    }
+
+    /// Whether another attempt follows the given attempt, counting from 1.
+    pub(crate) fn has_attempts_left(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    /// The delay before the attempt following the given attempt, counting from 1.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .checked_pow(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        let backoff = self
+            .initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            backoff - backoff.mul_f64(rand::thread_rng().gen_range(0.0..0.5))
+        } else {
+            backoff
+        }
+    }
+}
+
+/// Whether an I/O error is a lost connection to the daemon, rather than one that fails again.
+fn is_connection_error(kind: ErrorKind) -> bool {
+    matches!(
+        kind,
+        ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::UnexpectedEof
+    )
+}
+
+/// The kind of the first I/O error in the chain of sources of an error.
+fn io_error_kind(err: &(dyn StdError + 'static)) -> Option<ErrorKind> {
+    iter::successors(Some(err), |&err| err.source())
+        .find_map(|err| err.downcast_ref::<io::Error>())
+        .map(io::Error::kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    use hyper::{Body, Request, Response};
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn flaky_docker(failures: usize, calls: Arc<AtomicUsize>) -> Docker {
+        Docker::connect_with_transport(
+            move |_request: Request<Body>| {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if call < failures {
+                        let mut response = Response::new(Body::from("daemon restarting"));
+                        *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+                        Ok(response)
+                    } else {
+                        Ok(Response::new(Body::from("OK")))
+                    }
+                }
+            },
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(300))
+            .jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+        assert_eq!(policy.backoff(40), Duration::from_millis(300));
+
+        let backoff = policy.jitter(true).backoff(2);
+        assert!(backoff > Duration::from_millis(100) && backoff <= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_retry_idempotent_requests() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let docker = flaky_docker(2, calls.clone())
+            .with_retry_policy(RetryPolicy::new().initial_backoff(Duration::from_millis(1)));
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let docker = flaky_docker(3, calls.clone())
+            .with_retry_policy(RetryPolicy::new().initial_backoff(Duration::from_millis(1)));
+        assert!(matches!(
+            docker.ping().await,
+            Err(DockerResponseServerError {
+                status_code: 503,
+                ..
+            })
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_retryable_io_errors() {
+        let policy = RetryPolicy::new();
+        let io_error = |kind| IOError {
+            err: io::Error::new(kind, "io error"),
+        };
+
+        assert!(policy.is_retryable_error(&io_error(ErrorKind::ConnectionRefused)));
+        assert!(policy.is_retryable_error(&io_error(ErrorKind::UnexpectedEof)));
+        assert!(!policy.is_retryable_error(&io_error(ErrorKind::PermissionDenied)));
+        assert!(!policy.is_retryable_error(&io_error(ErrorKind::NotFound)));
+        assert!(!policy
+            .retry_connection_errors(false)
+            .is_retryable_error(&io_error(ErrorKind::ConnectionReset)));
+    }
+
+    #[tokio::test]
+    async fn test_retry_opt_in_methods() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let docker = flaky_docker(1, calls.clone())
+            .with_retry_policy(RetryPolicy::new().initial_backoff(Duration::from_millis(1)));
+        assert!(docker.remove_container("my-container", None).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let docker = flaky_docker(1, calls.clone()).with_retry_policy(
+            RetryPolicy::new()
+                .initial_backoff(Duration::from_millis(1))
+                .retry_method(Method::DELETE),
+        );
+        docker.remove_container("my-container", None).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_streamed_bodies_are_not_retried() {
+        let policy = RetryPolicy::new().retry_method(Method::PUT);
+        let request = |body| Request::put("/archive").body(body).unwrap();
+
+        assert!(policy.applies_to(&request(Body::from(vec![0; 1024]))));
+        assert!(!policy.applies_to(&request(Body::from(vec![0; 2 * 1024 * 1024]))));
+
+        let chunks: Vec<Result<Vec<u8>, io::Error>> = vec![Ok(vec![0; 1024])];
+        let streamed = Body::wrap_stream(futures_util::stream::iter(chunks));
+        assert!(!policy.applies_to(&request(streamed)));
+    }
+
+    #[tokio::test]
+    async fn test_retryable_hyper_errors() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let docker =
+            Docker::connect_with_http(&format!("tcp://{}", addr), 120, API_DEFAULT_VERSION)
+                .unwrap();
+        let err = docker.ping().await.unwrap_err();
+        assert!(matches!(err, HyperResponseError { .. }));
+        assert!(RetryPolicy::new().is_retryable_error(&err));
+        assert!(!RetryPolicy::new()
+            .retry_connection_errors(false)
+            .is_retryable_error(&err));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_missing_socket_is_not_retried() {
+        let path = std::env::temp_dir().join("bollard-missing.sock");
+        let docker = Docker::connect_with_unix(path.to_str().unwrap(), 120, API_DEFAULT_VERSION)
+            .unwrap()
+            .with_retry_policy(
+                RetryPolicy::new()
+                    .initial_backoff(Duration::from_secs(60))
+                    .jitter(false),
+            );
+
+        // A retry would wait for the backoff before failing.
+        let err = tokio::time::timeout(Duration::from_secs(10), docker.ping())
+            .await
+            .expect("the request was retried")
+            .unwrap_err();
+        assert!(matches!(err, HyperResponseError { .. }));
+        assert!(!RetryPolicy::new().is_retryable_error(&err));
+    }
+}
@@ -0,0 +1,301 @@
+//! Secret API: Secrets are sensitive data that can be used by services. Swarm mode must be
+//! enabled for these endpoints to work.
+
+pub use crate::models::*;
+
+use super::Docker;
+use crate::docker::encode_spec_data;
+use crate::errors::Error;
+use http::request::Builder;
+use hyper::{body::Body as HyperBody, Method};
+use serde::ser::Serialize;
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Parameters used in the [List Secrets API](super::Docker::list_secrets())
+///
+/// ## Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// use bollard::secret::ListSecretsOptions;
+///
+/// let mut filters = HashMap::new();
+/// filters.insert("label", vec!["secret-label=label-value"]);
+///
+/// ListSecretsOptions{
+///     filters,
+/// };
+/// ```
+///
+/// ```rust
+/// # use bollard::secret::ListSecretsOptions;
+/// # use std::default::Default;
+///
+/// let options: ListSecretsOptions<&str> = Default::default();
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListSecretsOptions<T>
+where
+    T: Into<String> + Eq + Hash + Serialize,
+{
+    /// Filters to process on the secrets list, encoded as JSON. Available filters:
+    ///  - `id`=`<secret id>` a secret's ID
+    ///  - `label`=`key` or `label`=`"key=value"` of a secret label
+    ///  - `name`=`<secret name>` a secret's name
+    ///  - `names`=`<secret name>` a secret's name, matched exactly
+    #[serde(serialize_with = "crate::docker::serialize_as_json")]
+    pub filters: HashMap<T, Vec<T>>,
+}
+
+/// Parameters used in the [Update Secret API](Docker::update_secret())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::secret::UpdateSecretOptions;
+///
+/// UpdateSecretOptions{
+///     version: 1234,
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, Serialize)]
+pub struct UpdateSecretOptions {
+    /// The version number of the secret object being updated. This is required to avoid
+    /// conflicting writes.
+    pub version: u64,
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # List Secrets
+    ///
+    /// Returns a list of secrets.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [ListSecretsOptions](ListSecretsOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Secrets](Secret), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::secret::ListSecretsOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("label", vec!["secret-label=label-value"]);
+    ///
+    /// let options = Some(ListSecretsOptions{
+    ///     filters,
+    /// });
+    ///
+    /// docker.list_secrets(options);
+    /// ```
+    pub async fn list_secrets<T>(
+        &self,
+        options: Option<ListSecretsOptions<T>>,
+    ) -> Result<Vec<Secret>, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let url = "/secrets";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Create Secret
+    ///
+    /// Create a new secret on the docker swarm.
+    ///
+    /// # Arguments
+    ///
+    ///  - [SecretSpec](SecretSpec) struct.
+    ///  - Optional raw secret data. If set, it is base64 encoded into the `data` field of the
+    ///    spec, replacing any value already present.
+    ///
+    /// # Returns
+    ///
+    ///  - A [IdResponse](IdResponse) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::secret::SecretSpec;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let secret_spec = SecretSpec {
+    ///     name: Some(String::from("secret-name")),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.create_secret(secret_spec, Some("my secret password".as_bytes()));
+    /// ```
+    pub async fn create_secret(
+        &self,
+        mut secret_spec: SecretSpec,
+        data: Option<&[u8]>,
+    ) -> Result<IdResponse, Error> {
+        let url = "/secrets/create";
+
+        if let Some(data) = data {
+            secret_spec.data = Some(encode_spec_data(data));
+        }
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            None::<String>,
+            Docker::serialize_payload(Some(secret_spec)),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Inspect Secret
+    ///
+    /// Inspect a secret.
+    ///
+    /// # Arguments
+    ///
+    ///  - Secret id or name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - [Secret](Secret), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.inspect_secret("secret-id");
+    /// docker.inspect_secret("secret-name");
+    /// ```
+    pub async fn inspect_secret(&self, secret_id: &str) -> Result<Secret, Error> {
+        let url = format!("/secrets/{}", secret_id);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Update Secret
+    ///
+    /// Update an existing secret. Currently, only the `labels` field can be updated.
+    ///
+    /// # Arguments
+    ///
+    ///  - Secret id or name as a string slice.
+    ///  - [SecretSpec](SecretSpec) struct.
+    ///  - [UpdateSecretOptions](UpdateSecretOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::secret::UpdateSecretOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let result = async move {
+    ///     let existing = docker.inspect_secret("my-secret").await?;
+    ///     let version = existing.version.unwrap().index.unwrap();
+    ///     let mut spec = existing.spec.unwrap().clone();
+    ///
+    ///     let mut labels = HashMap::new();
+    ///     labels.insert(String::from("secret-label"), String::from("label-value"));
+    ///     spec.labels = Some(labels.clone());
+    ///
+    ///     let options = UpdateSecretOptions { version };
+    ///
+    ///     docker.update_secret("my-secret", spec, options).await
+    /// };
+    /// ```
+    pub async fn update_secret(
+        &self,
+        secret_id: &str,
+        secret_spec: SecretSpec,
+        options: UpdateSecretOptions,
+    ) -> Result<(), Error> {
+        let url = format!("/secrets/{}/update", secret_id);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::POST),
+            Some(options),
+            Docker::serialize_payload(Some(secret_spec)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Delete Secret
+    ///
+    /// Delete a secret, fails when more than one service use that secret.
+    ///
+    /// # Arguments
+    ///
+    ///  - Secret id or name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.delete_secret("secret-name");
+    /// ```
+    pub async fn delete_secret(&self, secret_id: &str) -> Result<(), Error> {
+        let url = format!("/secrets/{}", secret_id);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::DELETE),
+            None::<String>,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_unit(req).await
+    }
+}
@@ -4,10 +4,14 @@ pub use crate::models::*;
 
 use super::Docker;
 use crate::auth::{base64_url_encode, DockerCredentials};
+use crate::container::LogOutput;
 use crate::errors::Error;
+use chrono::{DateTime, Utc};
+use futures_core::Stream;
+use futures_util::stream::TryStreamExt;
 use http::header::CONTENT_TYPE;
 use http::request::Builder;
-use hyper::{Body, Method};
+use hyper::{body::Body as HyperBody, body::Bytes, Method};
 use serde::ser::Serialize;
 
 use std::{collections::HashMap, hash::Hash};
@@ -92,6 +96,150 @@ pub struct UpdateServiceOptions {
     pub rollback: bool,
 }
 
+/// Parameters used in the [Service Logs API](Docker::service_logs())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::service::ServiceLogsOptions;
+///
+/// use std::default::Default;
+///
+/// ServiceLogsOptions::<String>{
+///     stdout: true,
+///     details: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ServiceLogsOptions<T>
+where
+    T: Into<String> + Serialize,
+{
+    /// Show service context and extra details provided to logs, returned as
+    /// [ServiceLogDetails](ServiceLogDetails) with each log line.
+    pub details: bool,
+    /// Keep connection after returning logs.
+    pub follow: bool,
+    /// Return logs from `stdout`.
+    pub stdout: bool,
+    /// Return logs from `stderr`.
+    pub stderr: bool,
+    /// Only return logs since this time, as a UNIX timestamp.
+    pub since: i64,
+    /// Add timestamps to every log line.
+    pub timestamps: bool,
+    /// Only return this number of log lines from the end of the logs. Specify as an integer or all
+    /// to output `all` log lines.
+    pub tail: T,
+}
+
+/// A log line returned by the [Service Logs API](Docker::service_logs()), with the timestamp
+/// and labels that the daemon prepends split from the message.
+#[derive(Debug, Clone)]
+pub struct ServiceLogOutput {
+    /// Timestamp of the log line, present if the `timestamps` option was set.
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Labels of the log line, present if the `details` option was set.
+    pub details: Option<ServiceLogDetails>,
+    /// Remaining log message, after the timestamp and labels.
+    pub output: LogOutput,
+}
+
+/// The labels prepended to a log line returned with the `details` option.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceLogDetails {
+    /// ID of the node the task is running on (`com.docker.swarm.node.id`).
+    pub node_id: Option<String>,
+    /// ID of the service (`com.docker.swarm.service.id`).
+    pub service_id: Option<String>,
+    /// ID of the task that emitted the log line (`com.docker.swarm.task.id`).
+    pub task_id: Option<String>,
+    /// All labels prepended by the daemon, including the swarm IDs above and any extra
+    /// attributes configured on the log driver.
+    pub attrs: HashMap<String, String>,
+}
+
+impl ServiceLogOutput {
+    /// Split the timestamp and labels from a log line, as requested with the `timestamps` and
+    /// `details` options. Lines that do not carry the expected prefix are returned unchanged.
+    fn parse(output: LogOutput, timestamps: bool, details: bool) -> ServiceLogOutput {
+        let (line, with_message): (&Bytes, fn(Bytes) -> LogOutput) = match output {
+            LogOutput::StdErr { ref message } => (message, |message| LogOutput::StdErr { message }),
+            LogOutput::StdOut { ref message } => (message, |message| LogOutput::StdOut { message }),
+            LogOutput::StdIn { ref message } => (message, |message| LogOutput::StdIn { message }),
+            LogOutput::Console { ref message } => {
+                (message, |message| LogOutput::Console { message })
+            }
+        };
+
+        let mut rest = line.clone();
+
+        let timestamp = if timestamps {
+            split_token(&rest).and_then(|(token, remaining)| {
+                let timestamp = DateTime::parse_from_rfc3339(std::str::from_utf8(&token).ok()?)
+                    .ok()?
+                    .with_timezone(&Utc);
+                rest = remaining;
+                Some(timestamp)
+            })
+        } else {
+            None
+        };
+
+        let details = if details {
+            split_token(&rest)
+                .filter(|(token, _)| token.contains(&b'='))
+                .map(|(token, remaining)| {
+                    rest = remaining;
+                    ServiceLogDetails::parse(&token)
+                })
+        } else {
+            None
+        };
+
+        ServiceLogOutput {
+            timestamp,
+            details,
+            output: with_message(rest),
+        }
+    }
+}
+
+impl ServiceLogDetails {
+    /// Parse the comma separated, url encoded `key=value` labels of a log line.
+    fn parse(token: &[u8]) -> ServiceLogDetails {
+        let attrs: HashMap<String, String> = url::form_urlencoded::parse(
+            &token.split(|b| *b == b',').collect::<Vec<_>>().join(&b'&'),
+        )
+        .into_owned()
+        .collect();
+
+        ServiceLogDetails {
+            node_id: attrs.get("com.docker.swarm.node.id").cloned(),
+            service_id: attrs.get("com.docker.swarm.service.id").cloned(),
+            task_id: attrs.get("com.docker.swarm.task.id").cloned(),
+            attrs,
+        }
+    }
+}
+
+fn split_token(line: &Bytes) -> Option<(Bytes, Bytes)> {
+    let pos = line.iter().position(|b| *b == b' ')?;
+    Some((line.slice(..pos), line.slice(pos + 1..)))
+}
+
+/// The image reference the tasks of a service run.
+fn service_image(service_spec: &ServiceSpec) -> Option<&str> {
+    service_spec
+        .task_template
+        .as_ref()?
+        .container_spec
+        .as_ref()?
+        .image
+        .as_deref()
+}
+
 #[allow(clippy::trivially_copy_pass_by_ref)]
 pub(crate) fn serialize_registry_auth_from<S>(
     registry_auth_from: &bool,
@@ -163,7 +311,7 @@ impl Docker {
             url,
             Builder::new().method(Method::GET),
             options,
-            Ok(Body::empty()),
+            Ok(HyperBody::empty()),
         );
 
         self.process_into_value(req).await
@@ -183,7 +331,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Service Create Response](ServiceCreateResponse) struct,
-    ///  wrapped in a Future.
+    ///    wrapped in a Future.
     ///
     /// # Examples
     ///
@@ -228,9 +376,9 @@ impl Docker {
     ) -> Result<ServiceCreateResponse, Error> {
         let url = "/services/create";
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
-            ..Default::default()
-        })) {
+        match serde_json::to_string(
+            &self.resolve_registry_credentials(credentials, service_image(&service_spec)),
+        ) {
             Ok(ser_cred) => {
                 let req = self.build_request(
                     url,
@@ -287,7 +435,7 @@ impl Docker {
             &url,
             Builder::new().method(Method::GET),
             options,
-            Ok(Body::empty()),
+            Ok(HyperBody::empty()),
         );
 
         self.process_into_value(req).await
@@ -322,7 +470,7 @@ impl Docker {
             &url,
             Builder::new().method(Method::DELETE),
             None::<String>,
-            Ok(Body::empty()),
+            Ok(HyperBody::empty()),
         );
 
         self.process_into_unit(req).await
@@ -344,7 +492,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Service Update Response](ServiceUpdateResponse) struct,
-    ///  wrapped in a Future.
+    ///    wrapped in a Future.
     ///
     /// # Examples
     ///
@@ -397,9 +545,9 @@ impl Docker {
     ) -> Result<ServiceUpdateResponse, Error> {
         let url = format!("/services/{}/update", service_name);
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
-            ..Default::default()
-        })) {
+        match serde_json::to_string(
+            &self.resolve_registry_credentials(credentials, service_image(&service_spec)),
+        ) {
             Ok(ser_cred) => {
                 let req = self.build_request(
                     &url,
@@ -416,4 +564,117 @@ impl Docker {
             Err(e) => Err(e.into()),
         }
     }
+
+    /// ---
+    ///
+    /// # Service Logs
+    ///
+    /// Get `stdout` and `stderr` logs from all tasks of a service. This endpoint works only for
+    /// services with the `local`, `json-file` or `journald` logging drivers.
+    ///
+    /// # Arguments
+    ///
+    ///  - Service name or id as a string slice.
+    ///  - Optional [Service Logs Options](ServiceLogsOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Service Log Output](ServiceLogOutput) struct, wrapped in a Stream. With the `details`
+    ///    option, it carries the node, service and task labels of each line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::service::ServiceLogsOptions;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    /// use std::default::Default;
+    ///
+    /// let options = Some(ServiceLogsOptions::<String>{
+    ///     stdout: true,
+    ///     details: true,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// async move {
+    ///     let logs = docker.service_logs("my-service", options).try_collect::<Vec<_>>().await.unwrap();
+    ///     for line in logs {
+    ///         if let Some(details) = line.details {
+    ///             println!("{:?}: {}", details.task_id, line.output);
+    ///         }
+    ///     }
+    /// };
+    /// ```
+    pub fn service_logs<T>(
+        &self,
+        service_name: &str,
+        options: Option<ServiceLogsOptions<T>>,
+    ) -> impl Stream<Item = Result<ServiceLogOutput, Error>>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = format!("/services/{}/logs", service_name);
+        let (follow, timestamps, details) = options
+            .as_ref()
+            .map(|options| (options.follow, options.timestamps, options.details))
+            .unwrap_or_default();
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        let output = if follow {
+            self.for_silent_stream().process_into_stream_string(req)
+        } else {
+            self.process_into_stream_string(req)
+        };
+        output.map_ok(move |output| ServiceLogOutput::parse(output, timestamps, details))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_service_log_output() {
+        let output = LogOutput::StdOut {
+            message: Bytes::from(
+                "2021-11-20T10:00:00.000000000Z com.docker.swarm.node.id=abc,com.docker.swarm.service.id=def,com.docker.swarm.task.id=ghi,extra=a%20b hello world\n",
+            ),
+        };
+
+        let log = ServiceLogOutput::parse(output, true, true);
+        assert_eq!(
+            log.timestamp,
+            Some(
+                DateTime::parse_from_rfc3339("2021-11-20T10:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )
+        );
+        let details = log.details.unwrap();
+        assert_eq!(details.node_id.as_deref(), Some("abc"));
+        assert_eq!(details.service_id.as_deref(), Some("def"));
+        assert_eq!(details.task_id.as_deref(), Some("ghi"));
+        assert_eq!(details.attrs.get("extra").map(String::as_str), Some("a b"));
+        assert!(
+            matches!(log.output, LogOutput::StdOut { ref message } if &message[..] == b"hello world\n")
+        );
+
+        let output = LogOutput::StdErr {
+            message: Bytes::from("no labels here\n"),
+        };
+        let log = ServiceLogOutput::parse(output, false, true);
+        assert!(log.timestamp.is_none());
+        assert!(log.details.is_none());
+        assert!(
+            matches!(log.output, LogOutput::StdErr { ref message } if &message[..] == b"no labels here\n")
+        );
+    }
 }
@@ -0,0 +1,384 @@
+//! SSH transport: reach the daemon of an `ssh://[user@]host[:port]` url by running
+//! `docker system dial-stdio` on the host through `ssh`, and speaking HTTP over the stdio of that
+//! process. Another program taking the same arguments as `ssh` can be set with
+//! [`DockerBuilder::ssh_command`](crate::DockerBuilder::ssh_command()).
+
+use futures_util::ready;
+use hyper::client::connect::Connected;
+use pin_project::pin_project;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf};
+use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Sleep};
+use url::Url;
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// The program spawned to reach the remote daemon, unless the builder names another one.
+pub(crate) const DEFAULT_SSH_COMMAND: &str = "ssh";
+
+/// The number of bytes kept of the error output of the ssh command.
+const STDERR_LIMIT: usize = 4096;
+
+/// How long a failed connection waits for the ssh command to finish its error output.
+const STDERR_GRACE_PERIOD: Duration = Duration::from_millis(500);
+
+/// The arguments passed to the ssh command to reach the host of an `ssh://[user@]host[:port]`
+/// url, ending with the docker command that relays the daemon socket over stdio.
+pub(crate) fn ssh_args(addr: &str) -> Result<Vec<String>, io::Error> {
+    let url = Url::parse(addr).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let host = match url.host_str() {
+        Some(host) if url.scheme() == "ssh" => host,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid ssh url {}", addr),
+            ))
+        }
+    };
+
+    let mut args = vec![];
+    if !url.username().is_empty() {
+        args.push(String::from("-l"));
+        args.push(url.username().to_string());
+    }
+    if let Some(port) = url.port() {
+        args.push(String::from("-p"));
+        args.push(port.to_string());
+    }
+    args.push(String::from("--"));
+    args.push(host.to_string());
+    args.extend(
+        ["docker", "system", "dial-stdio"]
+            .iter()
+            .map(|arg| arg.to_string()),
+    );
+
+    Ok(args)
+}
+
+/// The error output of the ssh command, collected in the background so that a failing connection
+/// can report why ssh gave up, such as a rejected key or an unknown host.
+struct SshStderr {
+    output: Arc<Mutex<Vec<u8>>>,
+    reader: JoinHandle<()>,
+    grace_period: Option<Pin<Box<Sleep>>>,
+    reported: bool,
+}
+
+impl SshStderr {
+    fn collect(mut stderr: ChildStderr) -> SshStderr {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let collected = output.clone();
+        let reader = tokio::spawn(async move {
+            let mut chunk = [0u8; 512];
+            while let Ok(read) = stderr.read(&mut chunk).await {
+                if read == 0 {
+                    break;
+                }
+                if let Ok(mut output) = collected.lock() {
+                    let keep = read.min(STDERR_LIMIT.saturating_sub(output.len()));
+                    output.extend_from_slice(&chunk[..keep]);
+                }
+            }
+        });
+
+        SshStderr {
+            output,
+            reader,
+            grace_period: None,
+            reported: false,
+        }
+    }
+
+    /// Wait for the ssh command to close its error output, or the grace period to pass, and
+    /// return the output once.
+    fn poll_message(&mut self, cx: &mut Context<'_>) -> Poll<Option<String>> {
+        if self.reported {
+            return Poll::Ready(None);
+        }
+
+        let grace_period = self
+            .grace_period
+            .get_or_insert_with(|| Box::pin(sleep(STDERR_GRACE_PERIOD)));
+        if Pin::new(&mut self.reader).poll(cx).is_pending()
+            && grace_period.as_mut().poll(cx).is_pending()
+        {
+            return Poll::Pending;
+        }
+
+        self.reported = true;
+        let output = match self.output.lock() {
+            Ok(output) => String::from_utf8_lossy(&output).trim().to_string(),
+            Err(_) => String::new(),
+        };
+        Poll::Ready(Some(output).filter(|output| !output.is_empty()))
+    }
+
+    /// Replace the error of a failed connection with the error output of ssh, if there is any.
+    fn poll_error(&mut self, cx: &mut Context<'_>, err: io::Error) -> Poll<io::Error> {
+        Poll::Ready(match ready!(self.poll_message(cx)) {
+            Some(message) => io::Error::new(err.kind(), format!("ssh: {}", message)),
+            None => err,
+        })
+    }
+}
+
+/// HTTP connection over the stdio of a `docker system dial-stdio` process.
+#[pin_project]
+pub struct SshStream {
+    // Held so the process is killed once hyper drops the connection.
+    _child: Child,
+    #[pin]
+    stdin: ChildStdin,
+    #[pin]
+    stdout: ChildStdout,
+    stderr: SshStderr,
+}
+
+impl SshStream {
+    fn spawn(command: &str, args: &[String]) -> Result<SshStream, io::Error> {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::BrokenPipe, "ssh stdin unavailable"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::BrokenPipe, "ssh stdout unavailable"))?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::BrokenPipe, "ssh stderr unavailable"))?;
+
+        Ok(SshStream {
+            _child: child,
+            stdin,
+            stdout,
+            stderr: SshStderr::collect(stderr),
+        })
+    }
+}
+
+impl AsyncRead for SshStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.project();
+        let filled = buf.filled().len();
+        match ready!(this.stdout.poll_read(cx, buf)) {
+            // The end of the output of a connection that still expects data means ssh exited.
+            Ok(()) if buf.filled().len() == filled && buf.remaining() > 0 => {
+                match ready!(this.stderr.poll_message(cx)) {
+                    Some(message) => Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        format!("ssh: {}", message),
+                    ))),
+                    None => Poll::Ready(Ok(())),
+                }
+            }
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(err) => this.stderr.poll_error(cx, err).map(Err),
+        }
+    }
+}
+
+impl AsyncWrite for SshStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+        match ready!(this.stdin.poll_write(cx, buf)) {
+            Err(err) => this.stderr.poll_error(cx, err).map(Err),
+            written => Poll::Ready(written),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.project();
+        match ready!(this.stdin.poll_flush(cx)) {
+            Err(err) => this.stderr.poll_error(cx, err).map(Err),
+            flushed => Poll::Ready(flushed),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().stdin.poll_shutdown(cx)
+    }
+}
+
+impl hyper::client::connect::Connection for SshStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+/// Connector spawning one ssh process per connection. Connections are pooled by hyper, so
+/// consecutive requests reuse a running process.
+#[derive(Clone, Debug)]
+pub struct SshConnector {
+    command: Arc<String>,
+    args: Arc<Vec<String>>,
+}
+
+impl SshConnector {
+    pub(crate) fn new(command: &str, addr: &str) -> Result<SshConnector, io::Error> {
+        Ok(SshConnector {
+            command: Arc::new(command.to_string()),
+            args: Arc::new(ssh_args(addr)?),
+        })
+    }
+}
+
+impl hyper::service::Service<hyper::Uri> for SshConnector {
+    type Response = SshStream;
+    type Error = io::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _destination: hyper::Uri) -> Self::Future {
+        let command = self.command.clone();
+        let args = self.args.clone();
+
+        Box::pin(async move { SshStream::spawn(&command, &args) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssh_args() {
+        assert_eq!(
+            ssh_args("ssh://me@remote-host:2222").unwrap(),
+            vec![
+                "-l",
+                "me",
+                "-p",
+                "2222",
+                "--",
+                "remote-host",
+                "docker",
+                "system",
+                "dial-stdio"
+            ]
+        );
+        assert_eq!(
+            ssh_args("ssh://remote-host").unwrap(),
+            vec!["--", "remote-host", "docker", "system", "dial-stdio"]
+        );
+        assert!(ssh_args("tcp://remote-host").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_ssh_transport_reuses_connection() {
+        use crate::DockerBuilder;
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        // Stands in for ssh: records its arguments and each spawn, then answers every request
+        // on stdio with a fixed response.
+        let script = std::env::temp_dir().join(format!("bollard-ssh-{}", std::process::id()));
+        fs::write(
+            &script,
+            r#"#!/bin/sh
+echo "$@" > "$0.args"
+echo spawned >> "$0.spawns"
+while IFS= read -r line; do
+  if [ "$line" = "$(printf '\r')" ]; then
+    printf 'HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 2\r\n\r\nOK'
+  fi
+done
+"#,
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let docker = DockerBuilder::new()
+            .url("ssh://me@remote-host:2222")
+            .ssh_command(script.to_str().unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+
+        let args = fs::read_to_string(script.with_extension("args")).unwrap();
+        let spawns = fs::read_to_string(script.with_extension("spawns")).unwrap();
+
+        fs::remove_file(script.with_extension("args")).unwrap();
+        fs::remove_file(script.with_extension("spawns")).unwrap();
+        fs::remove_file(&script).unwrap();
+
+        assert_eq!(
+            args.trim(),
+            "-l me -p 2222 -- remote-host docker system dial-stdio"
+        );
+        assert_eq!(spawns.lines().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_ssh_transport_reports_stderr() {
+        use crate::DockerBuilder;
+        use std::error::Error as _;
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = std::env::temp_dir().join(format!("bollard-ssh-fail-{}", std::process::id()));
+        fs::write(
+            &script,
+            "#!/bin/sh\necho 'me@remote-host: Permission denied (publickey).' >&2\nexit 255\n",
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let docker = DockerBuilder::new()
+            .url("ssh://me@remote-host")
+            .ssh_command(script.to_str().unwrap())
+            .build()
+            .unwrap();
+
+        let err = docker.ping().await.unwrap_err();
+        fs::remove_file(&script).unwrap();
+
+        let mut messages = vec![err.to_string()];
+        let mut source = err.source();
+        while let Some(err) = source {
+            messages.push(err.to_string());
+            source = err.source();
+        }
+        assert!(
+            messages
+                .iter()
+                .any(|message| message.contains("Permission denied (publickey)")),
+            "{:?}",
+            messages
+        );
+    }
+}
@@ -0,0 +1,525 @@
+//! Swarm API: initialise, join and manage the swarm that this docker engine participates in
+
+pub use crate::models::*;
+
+use super::Docker;
+use crate::errors::Error;
+use http::request::Builder;
+use hyper::{body::Body as HyperBody, Method};
+use serde::ser::Serialize;
+
+/// Swarm configuration used in the [Init Swarm API](Docker::init_swarm())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::swarm::InitSwarmOptions;
+///
+/// use std::default::Default;
+///
+/// InitSwarmOptions {
+///     listen_addr: "0.0.0.0:2377",
+///     advertise_addr: "127.0.0.1",
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InitSwarmOptions<T>
+where
+    T: Into<String> + Serialize,
+{
+    /// Listen address used for inter-manager communication, as well as determining the networking
+    /// interface used for the VXLAN Tunnel Endpoint (VTEP). This can either be an address/port
+    /// combination in the form `192.168.1.1:4567`, or an interface followed by a port number, like
+    /// `eth0:4567`.
+    pub listen_addr: T,
+    /// Externally reachable address advertised to other nodes. This can either be an
+    /// address/port combination in the form `192.168.1.1:4567`, or an interface followed by a
+    /// port number, like `eth0:4567`.
+    pub advertise_addr: T,
+    /// Address or interface to use for data path traffic (format: `<ip|interface>`), for example,
+    /// `192.168.1.1`, or an interface, like `eth0`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_path_addr: Option<T>,
+    /// DataPathPort specifies the data path port number for data traffic. Acceptable port range
+    /// is 1024 to 49151. If no port is set or is set to 0, default port 4789 will be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_path_port: Option<u32>,
+    /// Default Address Pool specifies default subnet pools for global scope networks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_addr_pool: Option<Vec<T>>,
+    /// Force creation of a new swarm.
+    pub force_new_cluster: bool,
+    /// SubnetSize specifies the subnet size of the networks created from the default subnet pool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subnet_size: Option<u32>,
+    /// User modifiable swarm configuration.
+    pub spec: SwarmSpec,
+}
+
+/// Swarm configuration used in the [Join Swarm API](Docker::join_swarm())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::swarm::JoinSwarmOptions;
+///
+/// use std::default::Default;
+///
+/// JoinSwarmOptions {
+///     listen_addr: "0.0.0.0:2377",
+///     remote_addrs: vec!["node1:2377"],
+///     join_token: "SWMTKN-1-3pu6hszjas19xyp7ghgosyx9k8atbfcr8p2is99znpy26u2lkl-7p73s1dx5in4tatdymyhg9hu2",
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct JoinSwarmOptions<T>
+where
+    T: Into<String> + Serialize,
+{
+    /// Listen address used for inter-manager communication if the node gets promoted to manager,
+    /// as well as determining the networking interface used for the VXLAN Tunnel Endpoint (VTEP).
+    pub listen_addr: T,
+    /// Externally reachable address advertised to other nodes. This can either be an
+    /// address/port combination in the form `192.168.1.1:4567`, or an interface followed by a
+    /// port number, like `eth0:4567`. If the port number is omitted, the port number from the
+    /// listen address is used. If `AdvertiseAddr` is not specified, it will be automatically
+    /// detected when possible.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub advertise_addr: Option<T>,
+    /// Address or interface to use for data path traffic (format: `<ip|interface>`), for example,
+    /// `192.168.1.1`, or an interface, like `eth0`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_path_addr: Option<T>,
+    /// Addresses of manager nodes already participating in the swarm.
+    pub remote_addrs: Vec<T>,
+    /// Secret token for joining this swarm.
+    pub join_token: T,
+}
+
+/// Parameters used in the [Leave Swarm API](Docker::leave_swarm())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::swarm::LeaveSwarmOptions;
+///
+/// LeaveSwarmOptions {
+///     force: true,
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, Serialize)]
+pub struct LeaveSwarmOptions {
+    /// Force leave swarm, even if this is the last manager or that it will break the cluster.
+    pub force: bool,
+}
+
+/// Parameters used in the [Update Swarm API](Docker::update_swarm())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::swarm::UpdateSwarmOptions;
+///
+/// use std::default::Default;
+///
+/// UpdateSwarmOptions {
+///     version: 1234,
+///     rotate_worker_token: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSwarmOptions {
+    /// The version number of the swarm object being updated. This is required to avoid
+    /// conflicting writes.
+    pub version: u64,
+    /// Rotate the worker join token.
+    pub rotate_worker_token: bool,
+    /// Rotate the manager join token.
+    pub rotate_manager_token: bool,
+    /// Rotate the manager unlock key.
+    pub rotate_manager_unlock_key: bool,
+}
+
+/// Parameters used in the [Unlock Swarm API](Docker::unlock_swarm())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::swarm::UnlockSwarmOptions;
+///
+/// UnlockSwarmOptions {
+///     unlock_key: "SWMKEY-1-7c37Cc8654o6p38HnroywCi19pllOnGtbdZEgtKxZu8",
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UnlockSwarmOptions<T>
+where
+    T: Into<String> + Serialize,
+{
+    /// The swarm's unlock key.
+    pub unlock_key: T,
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Init Swarm
+    ///
+    /// Initialize a new swarm.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Init Swarm Options](InitSwarmOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - The node ID as a [String](std::string::String), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::swarm::InitSwarmOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let config = InitSwarmOptions {
+    ///     listen_addr: "0.0.0.0:2377",
+    ///     advertise_addr: "127.0.0.1",
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.init_swarm(config);
+    /// ```
+    pub async fn init_swarm<T>(&self, config: InitSwarmOptions<T>) -> Result<String, Error>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = "/swarm/init";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            None::<String>,
+            Docker::serialize_payload(Some(config)),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Join Swarm
+    ///
+    /// Join an existing swarm.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Join Swarm Options](JoinSwarmOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::swarm::JoinSwarmOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let config = JoinSwarmOptions {
+    ///     listen_addr: "0.0.0.0:2377",
+    ///     remote_addrs: vec!["node1:2377"],
+    ///     join_token: "SWMTKN-1-3pu6hszjas19xyp7ghgosyx9k8atbfcr8p2is99znpy26u2lkl-7p73s1dx5in4tatdymyhg9hu2",
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.join_swarm(config);
+    /// ```
+    pub async fn join_swarm<T>(&self, config: JoinSwarmOptions<T>) -> Result<(), Error>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = "/swarm/join";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            None::<String>,
+            Docker::serialize_payload(Some(config)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Leave Swarm
+    ///
+    /// Leave a swarm.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [Leave Swarm Options](LeaveSwarmOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::swarm::LeaveSwarmOptions;
+    ///
+    /// let options = Some(LeaveSwarmOptions {
+    ///     force: true,
+    /// });
+    ///
+    /// docker.leave_swarm(options);
+    /// ```
+    pub async fn leave_swarm(&self, options: Option<LeaveSwarmOptions>) -> Result<(), Error> {
+        let url = "/swarm/leave";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Inspect Swarm
+    ///
+    /// Inspect the swarm this node participates in.
+    ///
+    /// # Returns
+    ///
+    ///  - [Swarm](Swarm) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.inspect_swarm();
+    /// ```
+    pub async fn inspect_swarm(&self) -> Result<Swarm, Error> {
+        let url = "/swarm";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Update Swarm
+    ///
+    /// Update the swarm configuration, optionally rotating the join tokens or the unlock key.
+    ///
+    /// # Arguments
+    ///
+    ///  - [SwarmSpec](SwarmSpec) struct.
+    ///  - [Update Swarm Options](UpdateSwarmOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::swarm::UpdateSwarmOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let result = async move {
+    ///     let swarm = docker.inspect_swarm().await?;
+    ///     let options = UpdateSwarmOptions {
+    ///         version: swarm.version.unwrap().index.unwrap(),
+    ///         rotate_worker_token: true,
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     docker.update_swarm(swarm.spec.unwrap(), options).await
+    /// };
+    /// ```
+    pub async fn update_swarm(
+        &self,
+        spec: SwarmSpec,
+        options: UpdateSwarmOptions,
+    ) -> Result<(), Error> {
+        let url = "/swarm/update";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            Some(options),
+            Docker::serialize_payload(Some(spec)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Get Unlock Key
+    ///
+    /// Get the unlock key of a swarm that has autolock enabled.
+    ///
+    /// # Returns
+    ///
+    ///  - [Unlock Key Response](UnlockKeyResponse) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.get_unlock_key();
+    /// ```
+    pub async fn get_unlock_key(&self) -> Result<UnlockKeyResponse, Error> {
+        let url = "/swarm/unlockkey";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Unlock Swarm
+    ///
+    /// Unlock a locked manager.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Unlock Swarm Options](UnlockSwarmOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::swarm::UnlockSwarmOptions;
+    ///
+    /// let options = UnlockSwarmOptions {
+    ///     unlock_key: "SWMKEY-1-7c37Cc8654o6p38HnroywCi19pllOnGtbdZEgtKxZu8",
+    /// };
+    ///
+    /// docker.unlock_swarm(options);
+    /// ```
+    pub async fn unlock_swarm<T>(&self, options: UnlockSwarmOptions<T>) -> Result<(), Error>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = "/swarm/unlock";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            None::<String>,
+            Docker::serialize_payload(Some(options)),
+        );
+
+        self.process_into_unit(req).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::API_DEFAULT_VERSION;
+
+    use hyper::{Request, Response};
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_join_and_unlock_swarm() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = requests.clone();
+        let docker = Docker::connect_with_transport(
+            move |request: Request<HyperBody>| {
+                let recorded = recorded.clone();
+                async move {
+                    let path = request.uri().path().to_string();
+                    let body = hyper::body::to_bytes(request.into_body()).await?;
+                    let body: serde_json::Value = serde_json::from_slice(&body)?;
+                    recorded.lock().unwrap().push((path, body));
+                    Ok(Response::new(HyperBody::empty()))
+                }
+            },
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        docker
+            .join_swarm(JoinSwarmOptions {
+                listen_addr: "0.0.0.0:2377",
+                remote_addrs: vec!["node1:2377"],
+                join_token: "SWMTKN-1-token",
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        docker
+            .unlock_swarm(UnlockSwarmOptions {
+                unlock_key: "SWMKEY-1-key",
+            })
+            .await
+            .unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].0.ends_with("/swarm/join"));
+        assert_eq!(
+            requests[0].1,
+            serde_json::json!({
+                "ListenAddr": "0.0.0.0:2377",
+                "RemoteAddrs": ["node1:2377"],
+                "JoinToken": "SWMTKN-1-token",
+            })
+        );
+        assert!(requests[1].0.ends_with("/swarm/unlock"));
+        assert_eq!(
+            requests[1].1,
+            serde_json::json!({ "UnlockKey": "SWMKEY-1-key" })
+        );
+    }
+}
@@ -11,6 +11,7 @@ use std::collections::HashMap;
 use std::hash::Hash;
 
 use super::Docker;
+use crate::auth::DockerCredentials;
 use crate::errors::Error;
 use crate::models::*;
 
@@ -244,7 +245,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [System Events Response](SystemEventsResponse),
-    ///  wrapped in a Stream.
+    ///    wrapped in a Stream.
     ///
     /// # Examples
     ///
@@ -278,7 +279,7 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_stream(req)
+        self.for_silent_stream().process_into_stream(req)
     }
 
     /// ---
@@ -290,8 +291,8 @@ impl Docker {
     /// # Returns
     ///
     ///  - [System Data Usage
-    ///  Response](SystemDataUsageResponse), wrapped in a
-    ///  Future.
+    ///    Response](SystemDataUsageResponse), wrapped in a
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -312,4 +313,66 @@ impl Docker {
 
         self.process_into_value(req).await
     }
+
+    /// ---
+    ///
+    /// # Check Auth
+    ///
+    /// Validate credentials for a registry and, if available, get an identity token for accessing
+    /// the registry without password. If the registry returns an identity token and the
+    /// credentials name a `serveraddress`, the token is remembered by this client for that
+    /// registry. Later requests to pull or push an image of the same registry use the token when
+    /// they are given no credentials, or credentials without a secret.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Docker Credentials](DockerCredentials) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [System Auth Response](SystemAuthResponse), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::auth::DockerCredentials;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let credentials = DockerCredentials {
+    ///     username: Some("Jack".to_string()),
+    ///     password: Some("myverysecretpassword".to_string()),
+    ///     serveraddress: Some("localhost:5000".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.check_auth(credentials);
+    /// ```
+    pub async fn check_auth(
+        &self,
+        credentials: DockerCredentials,
+    ) -> Result<SystemAuthResponse, Error> {
+        let url = "/auth";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            None::<String>,
+            Docker::serialize_payload(Some(&credentials)),
+        );
+
+        let response: SystemAuthResponse = self.process_into_value(req).await?;
+
+        if let (Some(serveraddress), Some(identity_token)) =
+            (&credentials.serveraddress, &response.identity_token)
+        {
+            if !identity_token.is_empty() {
+                self.store_identity_token(serveraddress, identity_token);
+            }
+        }
+
+        Ok(response)
+    }
 }
@@ -0,0 +1,236 @@
+//! Task API: A task is a container running on a swarm. It is the atomic scheduling unit of swarm.
+//! Swarm mode must be enabled for these endpoints to work.
+
+pub use crate::models::*;
+
+use super::Docker;
+use crate::container::LogOutput;
+use crate::errors::Error;
+use futures_core::Stream;
+use http::request::Builder;
+use hyper::{body::Body as HyperBody, Method};
+use serde::ser::Serialize;
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Parameters used in the [List Tasks API](super::Docker::list_tasks())
+///
+/// ## Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// use bollard::task::ListTasksOptions;
+///
+/// let mut filters = HashMap::new();
+/// filters.insert("service", vec!["my-service"]);
+/// filters.insert("desired-state", vec!["running"]);
+///
+/// ListTasksOptions{
+///     filters,
+/// };
+/// ```
+///
+/// ```rust
+/// # use bollard::task::ListTasksOptions;
+/// # use std::default::Default;
+///
+/// let options: ListTasksOptions<&str> = Default::default();
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListTasksOptions<T>
+where
+    T: Into<String> + Eq + Hash + Serialize,
+{
+    /// Filters to process on the tasks list, encoded as JSON. Available filters:
+    ///  - `desired-state`=(`running`|`shutdown`|`accepted`)
+    ///  - `id`=`<task id>` a task's ID
+    ///  - `label`=`key` or `label`=`"key=value"` of a task label
+    ///  - `name`=`<task name>` a task's name
+    ///  - `node`=`<node id or name>` a node's ID or name
+    ///  - `service`=`<service name>` a service's name
+    #[serde(serialize_with = "crate::docker::serialize_as_json")]
+    pub filters: HashMap<T, Vec<T>>,
+}
+
+/// Parameters used in the [Task Logs API](Docker::task_logs())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::task::TaskLogsOptions;
+///
+/// use std::default::Default;
+///
+/// TaskLogsOptions::<String>{
+///     stdout: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TaskLogsOptions<T>
+where
+    T: Into<String> + Serialize,
+{
+    /// Show task context and extra details provided to logs.
+    pub details: bool,
+    /// Keep connection after returning logs.
+    pub follow: bool,
+    /// Return logs from `stdout`.
+    pub stdout: bool,
+    /// Return logs from `stderr`.
+    pub stderr: bool,
+    /// Only return logs since this time, as a UNIX timestamp.
+    pub since: i64,
+    /// Add timestamps to every log line.
+    pub timestamps: bool,
+    /// Only return this number of log lines from the end of the logs. Specify as an integer or all
+    /// to output `all` log lines.
+    pub tail: T,
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # List Tasks
+    ///
+    /// Returns a list of tasks.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [ListTasksOptions](ListTasksOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Tasks](Task), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::task::ListTasksOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("service", vec!["my-service"]);
+    ///
+    /// let options = Some(ListTasksOptions{
+    ///     filters,
+    /// });
+    ///
+    /// docker.list_tasks(options);
+    /// ```
+    pub async fn list_tasks<T>(
+        &self,
+        options: Option<ListTasksOptions<T>>,
+    ) -> Result<Vec<Task>, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let url = "/tasks";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Inspect Task
+    ///
+    /// Inspect a task.
+    ///
+    /// # Arguments
+    ///
+    ///  - Task id as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - [Task](Task), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.inspect_task("my-task-id");
+    /// ```
+    pub async fn inspect_task(&self, task_id: &str) -> Result<Task, Error> {
+        let url = format!("/tasks/{}", task_id);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(HyperBody::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Task Logs
+    ///
+    /// Get `stdout` and `stderr` logs from a task. This endpoint works only for services with the
+    /// `local`, `json-file` or `journald` logging drivers.
+    ///
+    /// # Arguments
+    ///
+    ///  - Task id as a string slice.
+    ///  - Optional [Task Logs Options](TaskLogsOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Log Output](LogOutput) enum, wrapped in a
+    ///    Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::task::TaskLogsOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let options = Some(TaskLogsOptions::<String>{
+    ///     stdout: true,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// docker.task_logs("my-task-id", options);
+    /// ```
+    pub fn task_logs<T>(
+        &self,
+        task_id: &str,
+        options: Option<TaskLogsOptions<T>>,
+    ) -> impl Stream<Item = Result<LogOutput, Error>>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = format!("/tasks/{}/logs", task_id);
+        #[allow(clippy::unnecessary_map_or)]
+        let follow = options.as_ref().map_or(false, |options| options.follow);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            options,
+            Ok(HyperBody::empty()),
+        );
+
+        if follow {
+            self.for_silent_stream().process_into_stream_string(req)
+        } else {
+            self.process_into_stream_string(req)
+        }
+    }
+}
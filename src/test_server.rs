@@ -0,0 +1,1219 @@
+//! Test server: an in-process fake of the Docker daemon, listening on a temporary unix socket and
+//! keeping its containers, images, networks, volumes and execs in memory.
+//!
+//! The fake implements the core endpoints with the generated models and the daemon's status
+//! codes, such as `404` for unknown objects, `409` for conflicts and `304` for containers that
+//! are already started or stopped, so that orchestration logic can be tested with `cargo test`.
+//! Containers do not run anything: started containers stay running until stopped, killed or
+//! waited on, and execs of `echo` print their arguments.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bollard::container::{Config, CreateContainerOptions};
+//! use bollard::image::CreateImageOptions;
+//! use bollard::test_server::TestServer;
+//!
+//! use futures_util::stream::TryStreamExt;
+//!
+//! # async fn run() -> Result<(), bollard::errors::Error> {
+//! let server = TestServer::start()?;
+//! let docker = server.docker()?;
+//!
+//! docker
+//!     .create_image(
+//!         Some(CreateImageOptions {
+//!             from_image: "hello-world",
+//!             ..Default::default()
+//!         }),
+//!         None,
+//!         None,
+//!     )
+//!     .try_collect::<Vec<_>>()
+//!     .await?;
+//!
+//! docker
+//!     .create_container(
+//!         Some(CreateContainerOptions { name: "hello" }),
+//!         Config {
+//!             image: Some("hello-world"),
+//!             ..Default::default()
+//!         },
+//!     )
+//!     .await?;
+//! docker.start_container::<String>("hello", None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{SecondsFormat, Utc};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use rand::RngCore;
+use serde::ser::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::net::UnixListener;
+use tokio::task::JoinHandle;
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::errors::Error;
+use crate::models::*;
+use crate::{Docker, API_DEFAULT_VERSION};
+
+/// The API version reported by the fake daemon.
+const API_VERSION: &str = "1.41";
+
+/// The shortest image id prefix that references an image, as with the daemon.
+const MIN_IMAGE_ID_PREFIX: usize = 6;
+
+/// Counter keeping the socket paths of servers in the same process apart.
+static SERVER_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// An in-process fake Docker daemon, stopped when dropped.
+#[derive(Debug)]
+pub struct TestServer {
+    socket_path: PathBuf,
+    handle: JoinHandle<()>,
+}
+
+impl TestServer {
+    /// Start a fake daemon with no containers, images, networks or volumes, listening on a new
+    /// socket in the temporary directory. Must be called from within a Tokio runtime.
+    pub fn start() -> Result<TestServer, Error> {
+        let socket_path = env::temp_dir().join(format!(
+            "bollard-test-server-{}-{}.sock",
+            std::process::id(),
+            SERVER_COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        if socket_path.exists() {
+            fs::remove_file(&socket_path)?;
+        }
+
+        let listener = UnixListener::bind(&socket_path)?;
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let handle = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = state.clone();
+                tokio::spawn(async move {
+                    let service = service_fn(move |req| handle(state.clone(), req));
+                    if let Err(e) = Http::new()
+                        .serve_connection(stream, service)
+                        .with_upgrades()
+                        .await
+                    {
+                        debug!("test server connection error: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(TestServer {
+            socket_path,
+            handle,
+        })
+    }
+
+    /// The path of the unix socket the fake daemon listens on.
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Connect a [Docker] client to the fake daemon.
+    pub fn docker(&self) -> Result<Docker, Error> {
+        Docker::connect_with_unix(
+            &self.socket_path.to_string_lossy(),
+            120,
+            API_DEFAULT_VERSION,
+        )
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+        let _ = fs::remove_file(&self.socket_path);
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    containers: Vec<ContainerInspectResponse>,
+    images: Vec<Image>,
+    networks: Vec<Network>,
+    volumes: Vec<Volume>,
+    execs: Vec<ExecInspectResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContainerCreateBody {
+    #[serde(flatten)]
+    config: ContainerConfig,
+    #[serde(rename = "HostConfig")]
+    host_config: Option<HostConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct NetworkCreateBody {
+    name: String,
+    driver: String,
+    internal: bool,
+    attachable: bool,
+    ingress: bool,
+    #[serde(rename = "EnableIPv6")]
+    enable_ipv6: bool,
+    options: HashMap<String, String>,
+    labels: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct NetworkConnectBody {
+    container: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct VolumeCreateBody {
+    name: String,
+    driver: String,
+    driver_opts: HashMap<String, String>,
+    labels: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct ExecStartBody {
+    detach: bool,
+}
+
+fn random_id() -> String {
+    let mut id = [0; 32];
+    rand::thread_rng().fill_bytes(&mut id);
+    hex::encode(id)
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+fn json<T: Serialize>(status: StatusCode, value: &T) -> Response<Body> {
+    let mut response = Response::new(Body::from(serde_json::to_vec(value).unwrap_or_default()));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert("content-type", "application/json".parse().unwrap());
+    response
+}
+
+fn empty(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
+
+fn error(status: StatusCode, message: String) -> Response<Body> {
+    json(status, &serde_json::json!({ "message": message }))
+}
+
+fn is_true(query: &HashMap<String, String>, key: &str) -> bool {
+    matches!(query.get(key).map(String::as_str), Some("1") | Some("true"))
+}
+
+/// Add the `latest` tag to image references without a tag or digest.
+fn normalize_image(reference: &str) -> String {
+    let name = reference.rsplit('/').next().unwrap_or(reference);
+    if name.contains(':') || name.contains('@') {
+        reference.to_string()
+    } else {
+        format!("{}:latest", reference)
+    }
+}
+
+#[allow(clippy::unnecessary_map_or)]
+impl State {
+    fn container(&mut self, id: &str) -> Option<&mut ContainerInspectResponse> {
+        let name = format!("/{}", id.trim_start_matches('/'));
+        let index = self
+            .containers
+            .iter()
+            .position(|c| c.id.as_deref() == Some(id) || c.name.as_deref() == Some(&name))
+            .or_else(|| {
+                let matches: Vec<usize> = (0..self.containers.len())
+                    .filter(|&i| {
+                        self.containers[i]
+                            .id
+                            .as_deref()
+                            .map_or(false, |c| c.starts_with(id))
+                    })
+                    .collect();
+                if matches.len() == 1 {
+                    Some(matches[0])
+                } else {
+                    None
+                }
+            })?;
+        self.containers.get_mut(index)
+    }
+
+    fn image(&self, reference: &str) -> Option<&Image> {
+        let tagged = normalize_image(reference);
+        let id = reference.trim_start_matches("sha256:");
+        let is_id_prefix = id.len() >= MIN_IMAGE_ID_PREFIX
+            && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+        self.images.iter().find(|image| {
+            image
+                .repo_tags
+                .as_ref()
+                .map_or(false, |tags| tags.contains(&tagged))
+                || (is_id_prefix && image.id.trim_start_matches("sha256:").starts_with(id))
+        })
+    }
+
+    fn network(&mut self, id: &str) -> Option<&mut Network> {
+        self.networks.iter_mut().find(|network| {
+            network.name.as_deref() == Some(id)
+                || network.id.as_deref().map_or(false, |n| n.starts_with(id))
+        })
+    }
+}
+
+fn no_such_container(id: &str) -> Response<Body> {
+    error(StatusCode::NOT_FOUND, format!("No such container: {}", id))
+}
+
+fn no_such_image(reference: &str) -> Response<Body> {
+    error(
+        StatusCode::NOT_FOUND,
+        format!("No such image: {}", reference),
+    )
+}
+
+fn set_status(container: &mut ContainerInspectResponse, status: ContainerStateStatusEnum) {
+    let state = container.state.get_or_insert_with(Default::default);
+    let running = status == ContainerStateStatusEnum::RUNNING;
+    if running {
+        state.started_at = Some(now());
+        state.pid = Some(1);
+    } else {
+        state.finished_at = Some(now());
+        state.pid = Some(0);
+    }
+    state.running = Some(running);
+    state.status = Some(status);
+}
+
+fn is_running(container: &ContainerInspectResponse) -> bool {
+    container
+        .state
+        .as_ref()
+        .and_then(|state| state.running)
+        .unwrap_or(false)
+}
+
+#[allow(clippy::unnecessary_map_or)]
+async fn handle(
+    state: Arc<Mutex<State>>,
+    mut req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let query: HashMap<String, String> =
+        url::form_urlencoded::parse(req.uri().query().unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect();
+
+    let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if segments
+        .first()
+        .map_or(false, |s| s.starts_with('v') && s[1..].contains('.'))
+    {
+        segments.remove(0);
+    }
+
+    let upgrade = match (&method, segments.as_slice()) {
+        (&Method::POST, ["exec", _, "start"]) => Some(hyper::upgrade::on(&mut req)),
+        _ => None,
+    };
+
+    let body = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(body) => body,
+        Err(e) => return Ok(error(StatusCode::BAD_REQUEST, e.to_string())),
+    };
+
+    let mut state = match state.lock() {
+        Ok(state) => state,
+        Err(_) => {
+            return Ok(error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                String::from("test server state poisoned"),
+            ))
+        }
+    };
+
+    let response = match (&method, segments.as_slice()) {
+        (&Method::GET, ["_ping"]) | (&Method::HEAD, ["_ping"]) => Response::new(Body::from("OK")),
+        (&Method::GET, ["version"]) => json(
+            StatusCode::OK,
+            &SystemVersion {
+                version: Some(String::from("20.10.0-test")),
+                api_version: Some(String::from(API_VERSION)),
+                min_api_version: Some(String::from("1.12")),
+                os: Some(String::from(env::consts::OS)),
+                arch: Some(String::from(env::consts::ARCH)),
+                ..Default::default()
+            },
+        ),
+
+        // Containers
+        (&Method::POST, ["containers", "create"]) => {
+            match serde_json::from_slice::<ContainerCreateBody>(&body) {
+                Ok(body) => create_container(&mut state, query.get("name"), body),
+                Err(e) => error(StatusCode::BAD_REQUEST, e.to_string()),
+            }
+        }
+        (&Method::GET, ["containers", "json"]) => {
+            let all = is_true(&query, "all");
+            let summaries: Vec<ContainerSummaryInner> = state
+                .containers
+                .iter()
+                .filter(|container| all || is_running(container))
+                .map(container_summary)
+                .collect();
+            json(StatusCode::OK, &summaries)
+        }
+        (&Method::GET, ["containers", id, "json"]) => match state.container(id) {
+            Some(container) => json(StatusCode::OK, container),
+            None => no_such_container(id),
+        },
+        (&Method::POST, ["containers", id, "start"]) => match state.container(id) {
+            Some(container) if is_running(container) => empty(StatusCode::NOT_MODIFIED),
+            Some(container) => {
+                set_status(container, ContainerStateStatusEnum::RUNNING);
+                empty(StatusCode::NO_CONTENT)
+            }
+            None => no_such_container(id),
+        },
+        (&Method::POST, ["containers", id, "stop"]) => match state.container(id) {
+            Some(container) if !is_running(container) => empty(StatusCode::NOT_MODIFIED),
+            Some(container) => {
+                set_status(container, ContainerStateStatusEnum::EXITED);
+                empty(StatusCode::NO_CONTENT)
+            }
+            None => no_such_container(id),
+        },
+        (&Method::POST, ["containers", id, "restart"]) => match state.container(id) {
+            Some(container) => {
+                set_status(container, ContainerStateStatusEnum::RUNNING);
+                if let Some(count) = container.restart_count.as_mut() {
+                    *count += 1;
+                }
+                empty(StatusCode::NO_CONTENT)
+            }
+            None => no_such_container(id),
+        },
+        (&Method::POST, ["containers", id, "kill"]) => match state.container(id) {
+            Some(container) if !is_running(container) => error(
+                StatusCode::CONFLICT,
+                format!("Container {} is not running", id),
+            ),
+            Some(container) => {
+                set_status(container, ContainerStateStatusEnum::EXITED);
+                if let Some(state) = container.state.as_mut() {
+                    state.exit_code = Some(137);
+                }
+                empty(StatusCode::NO_CONTENT)
+            }
+            None => no_such_container(id),
+        },
+        (&Method::POST, ["containers", id, "wait"]) => match state.container(id) {
+            Some(container) => {
+                // The fake runs nothing, so running containers exit as soon as they are waited on.
+                if is_running(container) {
+                    set_status(container, ContainerStateStatusEnum::EXITED);
+                }
+                let status_code = container
+                    .state
+                    .as_ref()
+                    .and_then(|state| state.exit_code)
+                    .unwrap_or_default();
+                json(
+                    StatusCode::OK,
+                    &ContainerWaitResponse {
+                        status_code,
+                        error: None,
+                    },
+                )
+            }
+            None => no_such_container(id),
+        },
+        (&Method::DELETE, ["containers", id]) => match state.container(id) {
+            Some(container) if is_running(container) && !is_true(&query, "force") => error(
+                StatusCode::CONFLICT,
+                format!(
+                    "You cannot remove a running container {}. Stop the container before \
+                     attempting removal or force remove",
+                    container.id.clone().unwrap_or_default()
+                ),
+            ),
+            Some(container) => {
+                let container_id = container.id.clone();
+                state.containers.retain(|c| c.id != container_id);
+                state.execs.retain(|e| e.container_id != container_id);
+                empty(StatusCode::NO_CONTENT)
+            }
+            None => no_such_container(id),
+        },
+        (&Method::POST, ["containers", id, "exec"]) => {
+            match serde_json::from_slice::<ExecConfig>(&body) {
+                Ok(config) => create_exec(&mut state, id, config),
+                Err(e) => error(StatusCode::BAD_REQUEST, e.to_string()),
+            }
+        }
+
+        // Execs
+        (&Method::POST, ["exec", id, "start"]) => {
+            let detach = serde_json::from_slice::<ExecStartBody>(&body)
+                .map(|body| body.detach)
+                .unwrap_or_default();
+            match state
+                .execs
+                .iter_mut()
+                .find(|exec| exec.id.as_deref() == Some(id))
+            {
+                Some(exec) => {
+                    exec.exit_code = Some(0);
+                    exec.running = Some(false);
+                    let output = exec_output(exec);
+                    match upgrade {
+                        Some(upgrade) if !detach => {
+                            tokio::spawn(async move {
+                                if let Ok(mut upgraded) = upgrade.await {
+                                    let _ = upgraded.write_all(output.as_bytes()).await;
+                                    let _ = upgraded.shutdown().await;
+                                }
+                            });
+                            let mut response = empty(StatusCode::SWITCHING_PROTOCOLS);
+                            response
+                                .headers_mut()
+                                .insert("connection", "Upgrade".parse().unwrap());
+                            response
+                                .headers_mut()
+                                .insert("upgrade", "tcp".parse().unwrap());
+                            response
+                        }
+                        _ => empty(StatusCode::OK),
+                    }
+                }
+                None => error(
+                    StatusCode::NOT_FOUND,
+                    format!("No such exec instance: {}", id),
+                ),
+            }
+        }
+        (&Method::GET, ["exec", id, "json"]) => {
+            match state
+                .execs
+                .iter()
+                .find(|exec| exec.id.as_deref() == Some(id))
+            {
+                Some(exec) => json(StatusCode::OK, exec),
+                None => error(
+                    StatusCode::NOT_FOUND,
+                    format!("No such exec instance: {}", id),
+                ),
+            }
+        }
+
+        // Images
+        (&Method::POST, ["images", "create"]) => match query
+            .get("fromImage")
+            .filter(|from_image| !from_image.is_empty())
+        {
+            Some(from_image) => {
+                let reference = match query.get("tag").filter(|tag| !tag.is_empty()) {
+                    Some(tag) => format!("{}:{}", from_image, tag),
+                    None => normalize_image(from_image),
+                };
+                pull_image(&mut state, &reference)
+            }
+            None => error(
+                StatusCode::BAD_REQUEST,
+                String::from("only pulling images with fromImage is supported"),
+            ),
+        },
+        (&Method::GET, ["images", "json"]) => {
+            let summaries: Vec<ImageSummary> = state
+                .images
+                .iter()
+                .map(|image| ImageSummary {
+                    id: image.id.clone(),
+                    repo_tags: image.repo_tags.clone().unwrap_or_default(),
+                    repo_digests: image.repo_digests.clone().unwrap_or_default(),
+                    size: image.size,
+                    virtual_size: image.virtual_size,
+                    containers: -1,
+                    shared_size: -1,
+                    ..Default::default()
+                })
+                .collect();
+            json(StatusCode::OK, &summaries)
+        }
+        (&Method::GET, ["images", name @ .., "json"]) if !name.is_empty() => {
+            let reference = name.join("/");
+            match state.image(&reference) {
+                Some(image) => json(StatusCode::OK, image),
+                None => no_such_image(&reference),
+            }
+        }
+        (&Method::DELETE, ["images", name @ ..]) if !name.is_empty() => {
+            let reference = name.join("/");
+            remove_image(&mut state, &reference, is_true(&query, "force"))
+        }
+
+        // Networks
+        (&Method::GET, ["networks"]) => json(StatusCode::OK, &state.networks),
+        (&Method::POST, ["networks", "create"]) => {
+            match serde_json::from_slice::<NetworkCreateBody>(&body) {
+                Ok(body) => create_network(&mut state, body),
+                Err(e) => error(StatusCode::BAD_REQUEST, e.to_string()),
+            }
+        }
+        (&Method::GET, ["networks", id]) => match state.network(id) {
+            Some(network) => json(StatusCode::OK, network),
+            None => error(StatusCode::NOT_FOUND, format!("network {} not found", id)),
+        },
+        (&Method::DELETE, ["networks", id]) => match state.network(id).map(|n| n.id.clone()) {
+            Some(network_id) => {
+                state.networks.retain(|n| n.id != network_id);
+                empty(StatusCode::NO_CONTENT)
+            }
+            None => error(StatusCode::NOT_FOUND, format!("network {} not found", id)),
+        },
+        (&Method::POST, ["networks", id, action @ "connect"])
+        | (&Method::POST, ["networks", id, action @ "disconnect"]) => {
+            let body = serde_json::from_slice::<NetworkConnectBody>(&body).unwrap_or_default();
+            connect_network(&mut state, id, &body.container, *action == "connect")
+        }
+
+        // Volumes
+        (&Method::GET, ["volumes"]) => json(
+            StatusCode::OK,
+            &VolumeListResponse {
+                volumes: state.volumes.clone(),
+                warnings: vec![],
+            },
+        ),
+        (&Method::POST, ["volumes", "create"]) => {
+            match serde_json::from_slice::<VolumeCreateBody>(&body) {
+                Ok(body) => create_volume(&mut state, body),
+                Err(e) => error(StatusCode::BAD_REQUEST, e.to_string()),
+            }
+        }
+        (&Method::GET, ["volumes", name]) => {
+            match state.volumes.iter().find(|volume| volume.name == *name) {
+                Some(volume) => json(StatusCode::OK, volume),
+                None => error(
+                    StatusCode::NOT_FOUND,
+                    format!("get {}: no such volume", name),
+                ),
+            }
+        }
+        (&Method::DELETE, ["volumes", name]) => {
+            if state.volumes.iter().any(|volume| volume.name == *name) {
+                state.volumes.retain(|volume| volume.name != *name);
+                empty(StatusCode::NO_CONTENT)
+            } else {
+                error(
+                    StatusCode::NOT_FOUND,
+                    format!("get {}: no such volume", name),
+                )
+            }
+        }
+
+        _ => error(StatusCode::NOT_FOUND, String::from("page not found")),
+    };
+
+    Ok(response)
+}
+
+fn create_container(
+    state: &mut State,
+    name: Option<&String>,
+    body: ContainerCreateBody,
+) -> Response<Body> {
+    let reference = body.config.image.clone().unwrap_or_default();
+    let image_id = match state.image(&reference) {
+        Some(image) => image.id.clone(),
+        None => return no_such_image(&reference),
+    };
+
+    let id = random_id();
+    let name = format!(
+        "/{}",
+        name.cloned()
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("test_{}", &id[..12]))
+    );
+    if state
+        .containers
+        .iter()
+        .any(|c| c.name.as_deref() == Some(&name))
+    {
+        return error(
+            StatusCode::CONFLICT,
+            format!(
+                "Conflict. The container name \"{}\" is already in use by another container.",
+                name
+            ),
+        );
+    }
+
+    let cmd = body.config.cmd.clone().unwrap_or_default();
+    state.containers.push(ContainerInspectResponse {
+        id: Some(id.clone()),
+        created: Some(now()),
+        path: cmd.first().cloned(),
+        args: Some(cmd.iter().skip(1).cloned().collect()),
+        state: Some(ContainerState {
+            status: Some(ContainerStateStatusEnum::CREATED),
+            running: Some(false),
+            paused: Some(false),
+            restarting: Some(false),
+            oom_killed: Some(false),
+            dead: Some(false),
+            pid: Some(0),
+            exit_code: Some(0),
+            ..Default::default()
+        }),
+        image: Some(image_id),
+        name: Some(name),
+        restart_count: Some(0),
+        driver: Some(String::from("overlay2")),
+        platform: Some(String::from("linux")),
+        host_config: body.host_config,
+        config: Some(body.config),
+        ..Default::default()
+    });
+
+    json(
+        StatusCode::CREATED,
+        &ContainerCreateResponse {
+            id,
+            warnings: vec![],
+        },
+    )
+}
+
+fn container_summary(container: &ContainerInspectResponse) -> ContainerSummaryInner {
+    let state = container.state.clone().unwrap_or_default();
+    let config = container.config.clone().unwrap_or_default();
+    ContainerSummaryInner {
+        id: container.id.clone(),
+        names: container.name.clone().map(|name| vec![name]),
+        image: config.image,
+        image_id: container.image.clone(),
+        command: config.cmd.map(|cmd| cmd.join(" ")),
+        created: container
+            .created
+            .as_ref()
+            .and_then(|created| chrono::DateTime::parse_from_rfc3339(created).ok())
+            .map(|created| created.timestamp()),
+        labels: config.labels,
+        state: state.status.map(|status| status.to_string()),
+        status: Some(if state.running.unwrap_or(false) {
+            String::from("Up")
+        } else {
+            format!("Exited ({})", state.exit_code.unwrap_or_default())
+        }),
+        ..Default::default()
+    }
+}
+
+fn create_exec(state: &mut State, id: &str, config: ExecConfig) -> Response<Body> {
+    let container_id = match state.container(id) {
+        Some(container) if !is_running(container) => {
+            return error(
+                StatusCode::CONFLICT,
+                format!("Container {} is not running", id),
+            )
+        }
+        Some(container) => container.id.clone(),
+        None => return no_such_container(id),
+    };
+
+    let exec_id = random_id();
+    let cmd = config.cmd.unwrap_or_default();
+    state.execs.push(ExecInspectResponse {
+        id: Some(exec_id.clone()),
+        running: Some(false),
+        container_id,
+        process_config: Some(ProcessConfig {
+            tty: config.tty,
+            user: config.user,
+            privileged: config.privileged,
+            entrypoint: cmd.first().cloned(),
+            arguments: Some(cmd.iter().skip(1).cloned().collect()),
+        }),
+        open_stdin: config.attach_stdin,
+        open_stdout: config.attach_stdout,
+        open_stderr: config.attach_stderr,
+        ..Default::default()
+    });
+    if let Some(container) = state.container(id) {
+        container
+            .exec_ids
+            .get_or_insert_with(Vec::new)
+            .push(exec_id.clone());
+    }
+
+    json(StatusCode::CREATED, &IdResponse { id: exec_id })
+}
+
+fn exec_output(exec: &ExecInspectResponse) -> String {
+    match exec.process_config {
+        Some(ProcessConfig {
+            entrypoint: Some(ref entrypoint),
+            arguments: Some(ref arguments),
+            ..
+        }) if entrypoint == "echo" => format!("{}\n", arguments.join(" ")),
+        _ => String::new(),
+    }
+}
+
+fn pull_image(state: &mut State, reference: &str) -> Response<Body> {
+    if state.image(reference).is_none() {
+        let id = format!("sha256:{}", random_id());
+        let repository = reference.rsplitn(2, ':').last().unwrap_or(reference);
+        state.images.push(Image {
+            id: id.clone(),
+            repo_tags: Some(vec![reference.to_string()]),
+            repo_digests: Some(vec![format!("{}@{}", repository, id)]),
+            created: now(),
+            architecture: String::from(env::consts::ARCH),
+            os: String::from("linux"),
+            root_fs: ImageRootFs {
+                typ: String::from("layers"),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+
+    let tag = reference.rsplit(':').next().unwrap_or("latest");
+    let progress = [
+        serde_json::json!({ "status": format!("Pulling from {}", reference), "id": tag }),
+        serde_json::json!({ "status": "Pull complete", "id": tag }),
+        serde_json::json!({
+            "status": format!("Status: Downloaded newer image for {}", reference)
+        }),
+    ];
+    let lines: String = progress.iter().map(|line| format!("{}\n", line)).collect();
+
+    let mut response = Response::new(Body::from(lines));
+    response
+        .headers_mut()
+        .insert("content-type", "application/json".parse().unwrap());
+    response
+}
+
+fn remove_image(state: &mut State, reference: &str, force: bool) -> Response<Body> {
+    let image = match state.image(reference) {
+        Some(image) => image.clone(),
+        None => return no_such_image(reference),
+    };
+
+    if !force {
+        if let Some(container) = state
+            .containers
+            .iter()
+            .find(|c| c.image.as_deref() == Some(&image.id))
+        {
+            return error(
+                StatusCode::CONFLICT,
+                format!(
+                    "conflict: unable to remove repository reference \"{}\" (must force) - \
+                     container {} is using its referenced image {}",
+                    reference,
+                    container.id.as_deref().unwrap_or_default(),
+                    image.id
+                ),
+            );
+        }
+    }
+
+    state.images.retain(|i| i.id != image.id);
+    let mut items: Vec<ImageDeleteResponseItem> = image
+        .repo_tags
+        .unwrap_or_default()
+        .into_iter()
+        .map(|tag| ImageDeleteResponseItem {
+            untagged: Some(tag),
+            deleted: None,
+        })
+        .collect();
+    items.push(ImageDeleteResponseItem {
+        untagged: None,
+        deleted: Some(image.id),
+    });
+
+    json(StatusCode::OK, &items)
+}
+
+fn create_network(state: &mut State, body: NetworkCreateBody) -> Response<Body> {
+    if state
+        .networks
+        .iter()
+        .any(|n| n.name.as_deref() == Some(&body.name))
+    {
+        return error(
+            StatusCode::CONFLICT,
+            format!("network with name {} already exists", body.name),
+        );
+    }
+
+    let id = random_id();
+    state.networks.push(Network {
+        name: Some(body.name),
+        id: Some(id.clone()),
+        created: Some(Utc::now()),
+        scope: Some(String::from("local")),
+        driver: Some(if body.driver.is_empty() {
+            String::from("bridge")
+        } else {
+            body.driver
+        }),
+        enable_ipv6: Some(body.enable_ipv6),
+        internal: Some(body.internal),
+        attachable: Some(body.attachable),
+        ingress: Some(body.ingress),
+        containers: Some(HashMap::new()),
+        options: Some(body.options),
+        labels: Some(body.labels),
+        ..Default::default()
+    });
+
+    json(
+        StatusCode::CREATED,
+        &NetworkCreateResponse {
+            id: Some(id),
+            warning: Some(String::new()),
+        },
+    )
+}
+
+fn connect_network(
+    state: &mut State,
+    network_id: &str,
+    container_id: &str,
+    connect: bool,
+) -> Response<Body> {
+    let container = match state.container(container_id) {
+        Some(container) => (
+            container.id.clone().unwrap_or_default(),
+            container.name.clone(),
+        ),
+        None => return no_such_container(container_id),
+    };
+
+    let network = match state.network(network_id) {
+        Some(network) => network,
+        None => {
+            return error(
+                StatusCode::NOT_FOUND,
+                format!("network {} not found", network_id),
+            )
+        }
+    };
+
+    let containers = network.containers.get_or_insert_with(HashMap::new);
+    if connect {
+        containers.insert(
+            container.0,
+            NetworkContainer {
+                name: container
+                    .1
+                    .map(|name| name.trim_start_matches('/').to_string()),
+                endpoint_id: Some(random_id()),
+                ..Default::default()
+            },
+        );
+    } else if containers.remove(&container.0).is_none() {
+        return error(
+            StatusCode::FORBIDDEN,
+            format!(
+                "container {} is not connected to network {}",
+                container_id, network_id
+            ),
+        );
+    }
+
+    empty(StatusCode::OK)
+}
+
+fn create_volume(state: &mut State, body: VolumeCreateBody) -> Response<Body> {
+    let name = if body.name.is_empty() {
+        random_id()
+    } else {
+        body.name
+    };
+
+    if let Some(volume) = state.volumes.iter().find(|volume| volume.name == name) {
+        return json(StatusCode::CREATED, volume);
+    }
+
+    let volume = Volume {
+        mountpoint: format!("/var/lib/docker/volumes/{}/_data", name),
+        name,
+        driver: if body.driver.is_empty() {
+            String::from("local")
+        } else {
+            body.driver
+        },
+        created_at: Some(Utc::now()),
+        labels: body.labels,
+        scope: Some(VolumeScopeEnum::LOCAL),
+        options: body.driver_opts,
+        ..Default::default()
+    };
+    state.volumes.push(volume.clone());
+
+    json(StatusCode::CREATED, &volume)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::{
+        Config, CreateContainerOptions, InspectContainerOptions, ListContainersOptions,
+        RemoveContainerOptions,
+    };
+    use crate::errors::Error::*;
+    use crate::exec::{CreateExecOptions, StartExecResults};
+    use crate::image::CreateImageOptions;
+    use crate::network::CreateNetworkOptions;
+    use crate::volume::CreateVolumeOptions;
+
+    use futures_util::stream::TryStreamExt;
+
+    async fn pull(docker: &Docker, image: &str) {
+        docker
+            .create_image(
+                Some(CreateImageOptions {
+                    from_image: image,
+                    ..Default::default()
+                }),
+                None,
+                None,
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_container_lifecycle() {
+        let server = TestServer::start().unwrap();
+        let docker = server.docker().unwrap();
+
+        let config = Config {
+            image: Some("hello-world"),
+            cmd: Some(vec!["/hello"]),
+            ..Default::default()
+        };
+        let options = Some(CreateContainerOptions { name: "hello" });
+
+        assert!(matches!(
+            docker
+                .create_container(options.clone(), config.clone())
+                .await,
+            Err(DockerResponseNotFoundError { .. })
+        ));
+
+        pull(&docker, "hello-world").await;
+        let created = docker
+            .create_container(options.clone(), config.clone())
+            .await
+            .unwrap();
+        assert!(matches!(
+            docker.create_container(options, config).await,
+            Err(DockerResponseConflictError { .. })
+        ));
+
+        docker
+            .start_container::<String>("hello", None)
+            .await
+            .unwrap();
+        assert!(matches!(
+            docker.start_container::<String>("hello", None).await,
+            Err(DockerResponseNotModifiedError { .. })
+        ));
+
+        let inspect = docker
+            .inspect_container("hello", None::<InspectContainerOptions>)
+            .await
+            .unwrap();
+        assert_eq!(inspect.id, Some(created.id.clone()));
+        assert_eq!(inspect.state.unwrap().running, Some(true));
+
+        let running = docker
+            .list_containers::<String>(None::<ListContainersOptions<String>>)
+            .await
+            .unwrap();
+        assert_eq!(running.len(), 1);
+
+        assert!(matches!(
+            docker.remove_container("hello", None).await,
+            Err(DockerResponseConflictError { .. })
+        ));
+        assert!(matches!(
+            docker.remove_image("hello-world", None, None).await,
+            Err(DockerResponseConflictError { .. })
+        ));
+
+        let exec = docker
+            .create_exec(
+                "hello",
+                CreateExecOptions {
+                    attach_stdout: Some(true),
+                    cmd: Some(vec!["echo", "hi"]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        match docker.start_exec(&exec.id, None).await.unwrap() {
+            StartExecResults::Attached { output, .. } => {
+                let output: Vec<String> = output
+                    .map_ok(|log| log.to_string())
+                    .try_collect()
+                    .await
+                    .unwrap();
+                assert_eq!(output, vec![String::from("hi\n")]);
+            }
+            StartExecResults::Detached => panic!("exec should be attached"),
+        }
+        assert_eq!(
+            docker.inspect_exec(&exec.id).await.unwrap().exit_code,
+            Some(0)
+        );
+
+        docker.stop_container("hello", None).await.unwrap();
+        docker
+            .remove_container("hello", None::<RemoveContainerOptions>)
+            .await
+            .unwrap();
+        assert!(matches!(
+            docker.inspect_container("hello", None).await,
+            Err(DockerResponseNotFoundError { .. })
+        ));
+        assert_eq!(
+            docker
+                .remove_image("hello-world", None, None)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_images() {
+        let server = TestServer::start().unwrap();
+        let docker = server.docker().unwrap();
+
+        pull(&docker, "hello-world").await;
+        pull(&docker, "busybox").await;
+
+        let image = docker.inspect_image("hello-world").await.unwrap();
+        let id = image.id.trim_start_matches("sha256:");
+        assert_eq!(docker.inspect_image(&id[..12]).await.unwrap().id, image.id);
+        assert!(matches!(
+            docker.inspect_image(&id[..3]).await,
+            Err(DockerResponseNotFoundError { .. })
+        ));
+
+        assert!(matches!(
+            docker
+                .create_image(
+                    Some(CreateImageOptions {
+                        from_src: "-",
+                        repo: "imported",
+                        ..Default::default()
+                    }),
+                    Some(Body::from(vec![0; 1024])),
+                    None,
+                )
+                .try_collect::<Vec<_>>()
+                .await,
+            Err(DockerResponseBadParameterError { .. })
+        ));
+        assert_eq!(docker.list_images::<String>(None).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_networks_and_volumes() {
+        let server = TestServer::start().unwrap();
+        let docker = server.docker().unwrap();
+
+        let network = docker
+            .create_network(CreateNetworkOptions {
+                name: "test-network",
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            docker
+                .create_network(CreateNetworkOptions {
+                    name: "test-network",
+                    ..Default::default()
+                })
+                .await,
+            Err(DockerResponseConflictError { .. })
+        ));
+        let inspect = docker
+            .inspect_network::<String>("test-network", None)
+            .await
+            .unwrap();
+        assert_eq!(inspect.id, network.id);
+        docker.remove_network("test-network").await.unwrap();
+        assert!(docker
+            .list_networks::<String>(None)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let volume = docker
+            .create_volume(CreateVolumeOptions {
+                name: "test-volume",
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(volume.driver, "local");
+        assert_eq!(
+            docker
+                .list_volumes::<String>(None)
+                .await
+                .unwrap()
+                .volumes
+                .len(),
+            1
+        );
+        docker.remove_volume("test-volume", None).await.unwrap();
+        assert!(matches!(
+            docker.inspect_volume("test-volume").await,
+            Err(DockerResponseNotFoundError { .. })
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+//! Transport API: plug a custom means of communication with the Docker daemon into the
+//! [Docker](crate::Docker) client, for example a connector for another kind of socket, or a fake
+//! serving canned responses in unit tests.
+
+use hyper::client::connect::Connect;
+use hyper::client::Client;
+use hyper::{Body, Request, Response};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::errors::Error;
+
+/// A boxed future, as returned by the [DockerTransport] methods.
+pub type TransportFuture<T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send + 'static>>;
+
+/// A bidirectional connection, taken over from an HTTP connection after the daemon switched
+/// protocols, for example to attach to a container.
+pub trait UpgradedIo: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T> UpgradedIo for T where T: AsyncRead + AsyncWrite + Send + Unpin {}
+
+/// A means of sending requests to the Docker daemon.
+///
+/// This is implemented for a hyper [Client](hyper::Client) over any connector, and for closures
+/// taking a request and returning a future of the response.
+///
+/// # Examples
+///
+/// ```rust
+/// use bollard::{Docker, API_DEFAULT_VERSION};
+///
+/// use hyper::{Body, Request, Response};
+///
+/// let docker = Docker::connect_with_transport(
+///     |_request: Request<Body>| async {
+///         Ok(Response::new(Body::from("OK")))
+///     },
+///     "http://localhost",
+///     120,
+///     API_DEFAULT_VERSION,
+/// )
+/// .unwrap();
+///
+/// async move {
+///     assert_eq!(docker.ping().await.unwrap(), "OK");
+/// };
+/// ```
+pub trait DockerTransport: Send + Sync {
+    /// Send a request to the daemon, resolving once the response headers are received.
+    fn request(&self, request: Request<Body>) -> TransportFuture<Response<Body>>;
+
+    /// Take over the connection of a response that switched protocols. Defaults to hyper's
+    /// upgrade mechanism, which works for responses received through a hyper client.
+    fn upgrade(&self, response: Response<Body>) -> TransportFuture<Box<dyn UpgradedIo>> {
+        Box::pin(async move {
+            let upgraded: Box<dyn UpgradedIo> = Box::new(hyper::upgrade::on(response).await?);
+            Ok(upgraded)
+        })
+    }
+}
+
+impl<C> DockerTransport for Client<C, Body>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    fn request(&self, request: Request<Body>) -> TransportFuture<Response<Body>> {
+        let response = Client::request(self, request);
+        Box::pin(async move { Ok(response.await?) })
+    }
+}
+
+impl<F, Fut> DockerTransport for F
+where
+    F: Fn(Request<Body>) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<Response<Body>, Error>> + Send + 'static,
+{
+    fn request(&self, request: Request<Body>) -> TransportFuture<Response<Body>> {
+        Box::pin(self(request))
+    }
+}
+
+impl fmt::Debug for dyn DockerTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DockerTransport")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::{AttachContainerOptions, AttachContainerResults};
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    use futures_util::stream::StreamExt;
+    use hyper::StatusCode;
+    use std::sync::Mutex;
+    use tokio::io::{duplex, AsyncWriteExt, DuplexStream};
+
+    #[tokio::test]
+    async fn test_closure_transport() {
+        let docker = Docker::connect_with_transport(
+            |request: Request<Body>| async move {
+                assert!(request
+                    .uri()
+                    .path()
+                    .ends_with("/containers/my-container/json"));
+                Ok(Response::new(Body::from(
+                    r#"{"Id":"abc123","Name":"/my-container"}"#,
+                )))
+            },
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        let container = docker
+            .inspect_container("my-container", None)
+            .await
+            .unwrap();
+        assert_eq!(container.id, Some(String::from("abc123")));
+    }
+
+    struct UpgradingTransport {
+        io: Mutex<Option<DuplexStream>>,
+    }
+
+    impl DockerTransport for UpgradingTransport {
+        fn request(&self, _request: Request<Body>) -> TransportFuture<Response<Body>> {
+            Box::pin(async move {
+                let mut response = Response::new(Body::empty());
+                *response.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+                Ok(response)
+            })
+        }
+
+        fn upgrade(&self, _response: Response<Body>) -> TransportFuture<Box<dyn UpgradedIo>> {
+            let io = self.io.lock().unwrap().take().unwrap();
+            let upgraded: Box<dyn UpgradedIo> = Box::new(io);
+            Box::pin(async move { Ok(upgraded) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transport_upgrade() {
+        let (client, mut server) = duplex(64);
+        let docker = Docker::connect_with_transport(
+            UpgradingTransport {
+                io: Mutex::new(Some(client)),
+            },
+            "http://localhost",
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        let AttachContainerResults { mut output, .. } = docker
+            .attach_container(
+                "my-container",
+                Some(AttachContainerOptions::<String> {
+                    stdout: Some(true),
+                    stream: Some(true),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+
+        server.write_all(&[1, 0, 0, 0, 0, 0, 0, 3]).await.unwrap();
+        server.write_all(b"hi\n").await.unwrap();
+        drop(server);
+
+        assert_eq!(
+            output.next().await.unwrap().unwrap().to_string(),
+            String::from("hi\n")
+        );
+    }
+
+    #[cfg(all(unix, feature = "test-server"))]
+    #[tokio::test]
+    async fn test_unix_connector_transport() {
+        let server = crate::test_server::TestServer::start().unwrap();
+        let addr = format!("unix://{}", server.socket_path().display());
+        let docker = Docker::connect_with_transport(
+            Client::builder().build(hyperlocal::UnixConnector),
+            &addr,
+            120,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+    }
+}
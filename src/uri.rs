@@ -70,6 +70,11 @@ impl<'a> Uri<'a> {
             ClientType::Unix => hex::encode(socket.as_ref().to_string_lossy().as_bytes()),
             #[cfg(windows)]
             ClientType::NamedPipe => hex::encode(socket.as_ref().to_string_lossy().as_bytes()),
+            ClientType::Ssh => hex::encode(socket.as_ref().to_string_lossy().as_bytes()),
+            ClientType::Custom { scheme } if *scheme == "http" || *scheme == "https" => {
+                socket.as_ref().to_string_lossy().into_owned()
+            }
+            ClientType::Custom { .. } => hex::encode(socket.as_ref().to_string_lossy().as_bytes()),
         }
     }
 
@@ -82,6 +87,8 @@ impl<'a> Uri<'a> {
             ClientType::Unix => "unix",
             #[cfg(windows)]
             ClientType::NamedPipe => "net.pipe",
+            ClientType::Ssh => "ssh",
+            ClientType::Custom { scheme } => scheme,
         }
     }
 
@@ -86,8 +86,8 @@ where
 {
     /// Filters to process on the prune list, encoded as JSON.
     ///  - `label` (`label=<key>`, `label=<key>=<value>`, `label!=<key>`, or
-    ///  `label!=<key>=<value>`) Prune volumes with (or without, in case `label!=...` is used) the
-    ///  specified labels.
+    ///    `label!=<key>=<value>`) Prune volumes with (or without, in case `label!=...` is used) the
+    ///    specified labels.
     #[serde(serialize_with = "crate::docker::serialize_as_json")]
     pub filters: HashMap<T, Vec<T>>,
 }
@@ -104,7 +104,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Volume List Response]VolumeListResponse) struct, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -157,7 +157,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Volume](Volume) struct, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -203,7 +203,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Volume](Volume) struct, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
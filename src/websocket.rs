@@ -0,0 +1,135 @@
+use futures_core::Stream;
+use futures_util::sink::Sink;
+use futures_util::stream::{SplitSink, SplitStream, StreamExt};
+use futures_util::{future, ready};
+use hyper::body::Bytes;
+use rand::RngCore;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::AsyncWrite;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::WebSocketStream;
+
+use crate::errors::Error;
+use crate::errors::Error::*;
+use crate::transport::UpgradedIo;
+
+/// Generate a `Sec-WebSocket-Key` handshake nonce: 16 random bytes, base64 encoded.
+pub(crate) fn websocket_key() -> String {
+    let mut nonce = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    base64::encode(nonce)
+}
+
+/// Check the `Sec-WebSocket-Accept` header of a handshake response against the
+/// `Sec-WebSocket-Key` header of its request.
+pub(crate) fn validate_accept_key(key: &[u8], accept_key: Option<&[u8]>) -> Result<(), Error> {
+    let accept_key = accept_key.ok_or_else(|| MissingHeaderError {
+        header: String::from("Sec-WebSocket-Accept"),
+    })?;
+
+    if accept_key == derive_accept_key(key).as_bytes() {
+        Ok(())
+    } else {
+        Err(WebSocketAcceptKeyError {
+            accept_key: String::from_utf8_lossy(accept_key).into_owned(),
+        })
+    }
+}
+
+fn io_error(err: WsError) -> io::Error {
+    match err {
+        WsError::Io(err) => err,
+        #[allow(clippy::io_other_error)]
+        err => io::Error::new(io::ErrorKind::Other, err),
+    }
+}
+
+/// Turn the incoming half of a websocket into a stream of bytes. Both text and binary frames are
+/// forwarded, as the daemon switched from text to binary frames in API version 1.42.
+pub(crate) fn websocket_reader(
+    stream: SplitStream<WebSocketStream<Box<dyn UpgradedIo>>>,
+) -> impl Stream<Item = Result<Bytes, io::Error>> {
+    stream.filter_map(|message| {
+        future::ready(match message {
+            Ok(Message::Binary(payload)) => Some(Ok(Bytes::from(payload))),
+            Ok(Message::Text(payload)) => Some(Ok(Bytes::from(payload))),
+            Ok(_) => None,
+            Err(WsError::ConnectionClosed) | Err(WsError::AlreadyClosed) => None,
+            Err(err) => Some(Err(io_error(err))),
+        })
+    })
+}
+
+/// Byte writer over the outgoing half of a websocket, sending each write as a binary frame.
+pub(crate) struct WebSocketWriter {
+    sink: SplitSink<WebSocketStream<Box<dyn UpgradedIo>>, Message>,
+}
+
+impl WebSocketWriter {
+    pub(crate) fn new(
+        sink: SplitSink<WebSocketStream<Box<dyn UpgradedIo>>, Message>,
+    ) -> WebSocketWriter {
+        WebSocketWriter { sink }
+    }
+}
+
+impl AsyncWrite for WebSocketWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        ready!(Pin::new(&mut self.sink).poll_ready(cx)).map_err(io_error)?;
+        Pin::new(&mut self.sink)
+            .start_send(Message::Binary(buf.to_vec()))
+            .map_err(io_error)?;
+
+        // Push the frame out eagerly, as attach sessions rarely flush their input explicitly.
+        if let Poll::Ready(Err(err)) = Pin::new(&mut self.sink).poll_flush(cx) {
+            return Poll::Ready(Err(io_error(err)));
+        }
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Pin::new(&mut self.sink).poll_flush(cx).map_err(io_error)
+    }
+
+    fn poll_shutdown(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), io::Error>> {
+        Pin::new(&mut self.sink).poll_close(cx).map_err(io_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_websocket_key() {
+        let key = websocket_key();
+        assert_eq!(base64::decode(&key).unwrap().len(), 16);
+        assert_ne!(key, websocket_key());
+    }
+
+    #[test]
+    fn test_validate_accept_key() {
+        let key = b"dGhlIHNhbXBsZSBub25jZQ==";
+
+        assert!(validate_accept_key(key, Some(b"s3pPLMBiTxaQ9kYGzzhZRbK+xOo=")).is_ok());
+        assert!(matches!(
+            validate_accept_key(key, Some(b"dGhlIHNhbXBsZSBub25jZQ==")),
+            Err(WebSocketAcceptKeyError { .. })
+        ));
+        assert!(matches!(
+            validate_accept_key(key, None),
+            Err(MissingHeaderError { .. })
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+use bollard::errors::Error;
+use bollard::{config::*, Docker};
+
+use tokio::runtime::Runtime;
+
+use std::collections::HashMap;
+
+#[macro_use]
+mod common;
+use crate::common::*;
+
+async fn config_create_test(docker: Docker) -> Result<(), Error> {
+    let spec = ConfigSpec {
+        name: Some(String::from("config_create_test")),
+        ..Default::default()
+    };
+
+    // bytes that are not valid utf-8 survive the round trip through the daemon
+    let data = [0x00, 0x9f, 0x92, 0x96, 0xff];
+
+    let response = docker.create_config(spec, Some(&data)).await?;
+
+    let config = docker.inspect_config(&response.id).await?;
+
+    assert_eq!(
+        config.spec.as_ref().unwrap().data.as_ref().unwrap(),
+        "AJ+Slv8="
+    );
+
+    let mut filters = HashMap::new();
+    filters.insert("name", vec!["config_create_test"]);
+
+    let configs = docker
+        .list_configs(Some(ListConfigsOptions { filters }))
+        .await?;
+
+    assert_eq!(configs.len(), 1);
+    assert_eq!(configs.first().unwrap().id, config.id);
+
+    docker.delete_config(&response.id).await?;
+
+    Ok(())
+}
+
+async fn config_create_from_file_test(docker: Docker) -> Result<(), Error> {
+    let path = std::env::temp_dir().join("config_create_from_file_test.conf");
+    tokio::fs::write(&path, "worker_processes 1;").await?;
+
+    let response = docker
+        .create_config_from_file(Default::default(), &path)
+        .await?;
+
+    let config = docker.inspect_config(&response.id).await?;
+
+    assert_eq!(
+        config.spec.as_ref().unwrap().name.as_ref().unwrap(),
+        "config_create_from_file_test.conf"
+    );
+
+    docker.delete_config(&response.id).await?;
+    tokio::fs::remove_file(&path).await?;
+
+    Ok(())
+}
+
+async fn config_update_test(docker: Docker) -> Result<(), Error> {
+    let spec = ConfigSpec {
+        name: Some(String::from("config_update_test")),
+        ..Default::default()
+    };
+
+    let response = docker
+        .create_config(spec, Some("worker_processes 1;".as_bytes()))
+        .await?;
+
+    let config = docker.inspect_config(&response.id).await?;
+
+    // only the labels of a config can be updated, not its data
+    let spec = ConfigSpec {
+        data: Some(String::from("d29ya2VyX3Byb2Nlc3NlcyAyOw==")),
+        ..config.spec.clone().unwrap()
+    };
+    let options = UpdateConfigOptions {
+        version: config.version.as_ref().unwrap().index.unwrap(),
+    };
+
+    assert!(docker
+        .update_config(&response.id, spec, options)
+        .await
+        .is_err());
+
+    let mut labels = HashMap::new();
+    labels.insert(String::from("maintainer"), String::from("bollard"));
+    let spec = ConfigSpec {
+        labels: Some(labels.clone()),
+        ..config.spec.unwrap()
+    };
+    let options = UpdateConfigOptions {
+        version: config.version.unwrap().index.unwrap(),
+    };
+
+    docker.update_config(&response.id, spec, options).await?;
+
+    let config = docker.inspect_config(&response.id).await?;
+    let spec = config.spec.unwrap();
+
+    assert_eq!(spec.labels, Some(labels));
+    assert_eq!(spec.data.unwrap(), "d29ya2VyX3Byb2Nlc3NlcyAxOw==");
+
+    docker.delete_config(&response.id).await?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_create_config() {
+    connect_to_docker_and_run!(config_create_test);
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_create_config_from_file() {
+    connect_to_docker_and_run!(config_create_from_file_test);
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_update_config() {
+    connect_to_docker_and_run!(config_update_test);
+}
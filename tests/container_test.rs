@@ -4,7 +4,8 @@ use bollard::container::{
     AttachContainerOptions, AttachContainerResults, Config, CreateContainerOptions,
     DownloadFromContainerOptions, InspectContainerOptions, KillContainerOptions,
     ListContainersOptions, LogsOptions, PruneContainersOptions, RemoveContainerOptions,
-    RenameContainerOptions, RestartContainerOptions, StatsOptions, TopOptions,
+    RenameContainerOptions, ResizeContainerTtyOptions, RestartContainerOptions,
+    StartContainerOptions, StatContainerPathOptions, StatsOptions, TopOptions,
     UpdateContainerOptions, UploadToContainerOptions, WaitContainerOptions,
 };
 use bollard::errors::Error;
@@ -343,6 +344,163 @@ async fn attach_container_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn attach_container_websocket_test(docker: Docker) -> Result<(), Error> {
+    create_shell_daemon(&docker, "integration_test_attach_container_websocket").await?;
+
+    let unique_string = "bollard_unique_string";
+    let AttachContainerResults { output, mut input } = docker
+        .attach_container_websocket(
+            "integration_test_attach_container_websocket",
+            Some(AttachContainerOptions::<String> {
+                stream: Some(true),
+                stdout: Some(true),
+                stdin: Some(true),
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    input
+        .write_all(format!("echo {}\n", unique_string).as_bytes())
+        .await?;
+    input.write_all("exit\n".as_bytes()).await?;
+
+    let log = match tokio::time::timeout(tokio::time::Duration::from_secs(2), output.try_collect())
+        .await
+    {
+        Ok(res) => res?,
+        Err(_) => {
+            docker
+                .kill_container(
+                    "integration_test_attach_container_websocket",
+                    None::<KillContainerOptions<String>>,
+                )
+                .await?;
+            vec![]
+        }
+    };
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_attach_container_websocket",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_attach_container_websocket",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    let input_found = log
+        .iter()
+        .find(|val| val.to_string().contains(unique_string))
+        .is_some();
+
+    assert!(input_found);
+
+    Ok(())
+}
+
+async fn resize_container_test(docker: Docker) -> Result<(), Error> {
+    let image = format!("{}alpine", registry_http_addr());
+
+    let _ = &docker
+        .create_image(
+            Some(CreateImageOptions {
+                from_image: &image[..],
+                ..Default::default()
+            }),
+            None,
+            Some(integration_test_registry_credentials()),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let _ = &docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_resize_container_tty",
+            }),
+            Config {
+                image: Some(image),
+                tty: Some(true),
+                open_stdin: Some(true),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let _ = &docker
+        .start_container(
+            "integration_test_resize_container_tty",
+            None::<StartContainerOptions<String>>,
+        )
+        .await?;
+
+    let AttachContainerResults { output, mut input } = docker
+        .attach_container(
+            "integration_test_resize_container_tty",
+            Some(AttachContainerOptions::<String> {
+                stream: Some(true),
+                stdout: Some(true),
+                stdin: Some(true),
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    docker
+        .resize_container_tty(
+            "integration_test_resize_container_tty",
+            ResizeContainerTtyOptions {
+                width: 123,
+                height: 45,
+            },
+        )
+        .await?;
+
+    input.write_all("stty size\n".as_bytes()).await?;
+    input.write_all("exit\n".as_bytes()).await?;
+
+    let log = match tokio::time::timeout(tokio::time::Duration::from_secs(2), output.try_collect())
+        .await
+    {
+        Ok(res) => res?,
+        Err(_) => {
+            docker
+                .kill_container(
+                    "integration_test_resize_container_tty",
+                    None::<KillContainerOptions<String>>,
+                )
+                .await?;
+            vec![]
+        }
+    };
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_resize_container_tty",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_resize_container_tty",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    assert!(log.iter().any(|val| val.to_string().contains("45 123")));
+
+    Ok(())
+}
+
 async fn update_container_test(docker: Docker) -> Result<(), Error> {
     let update_options = UpdateContainerOptions::<String> {
         memory: Some(314572800),
@@ -560,6 +718,37 @@ async fn archive_container_test(docker: Docker) -> Result<(), Error> {
         )
         .await?;
 
+    let stat = docker
+        .stat_container_path(
+            "integration_test_archive_container",
+            StatContainerPathOptions {
+                path: if cfg!(windows) {
+                    "C:\\Windows\\Logs\\readme.txt"
+                } else {
+                    "/tmp/readme.txt"
+                },
+            },
+        )
+        .await?;
+
+    assert_eq!(stat.name, "readme.txt");
+    assert_eq!(stat.size, readme.len() as i64);
+    assert!(!stat.is_dir());
+
+    let not_found = docker
+        .stat_container_path(
+            "integration_test_archive_container",
+            StatContainerPathOptions {
+                path: "/does/not/exist",
+            },
+        )
+        .await;
+
+    assert!(matches!(
+        not_found,
+        Err(Error::DockerResponseNotFoundError { .. })
+    ));
+
     let res = docker.download_from_container(
         "integration_test_archive_container",
         Some(DownloadFromContainerOptions {
@@ -615,6 +804,55 @@ async fn archive_container_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn export_container_test(docker: Docker) -> Result<(), Error> {
+    let image = format!("{}alpine", registry_http_addr());
+
+    let _ = &docker
+        .create_image(
+            Some(CreateImageOptions {
+                from_image: &image[..],
+                ..Default::default()
+            }),
+            None,
+            Some(integration_test_registry_credentials()),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let _ = &docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_export_container",
+            }),
+            Config {
+                image: Some(&image[..]),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let res = docker.export_container("integration_test_export_container");
+
+    let bytes = concat_byte_stream(res).await?;
+
+    let mut a: tar::Archive<&[u8]> = tar::Archive::new(&bytes[..]);
+
+    let release_found = a
+        .entries()?
+        .any(|entry| entry.unwrap().path().unwrap().to_str() == Some("etc/alpine-release"));
+
+    assert!(release_found);
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_export_container",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    Ok(())
+}
+
 async fn inspect_container_test(docker: Docker) -> Result<(), Error> {
     create_daemon(&docker, "integration_test_inspect_container").await?;
     let result = &docker
@@ -828,6 +1066,12 @@ fn integration_test_archive_containers() {
     connect_to_docker_and_run!(archive_container_test);
 }
 
+#[test]
+#[cfg(unix)]
+fn integration_test_export_container() {
+    connect_to_docker_and_run!(export_container_test);
+}
+
 #[test]
 fn integration_test_inspect_containers() {
     connect_to_docker_and_run!(inspect_container_test);
@@ -842,3 +1086,15 @@ fn integration_test_mount_volume_containers() {
 fn integration_test_attach_container() {
     connect_to_docker_and_run!(attach_container_test);
 }
+
+#[test]
+#[cfg(unix)]
+fn integration_test_attach_container_websocket() {
+    connect_to_docker_and_run!(attach_container_websocket_test);
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_resize_container_tty() {
+    connect_to_docker_and_run!(resize_container_test);
+}
@@ -118,6 +118,21 @@ async fn prune_images_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn prune_build_test(docker: Docker) -> Result<(), Error> {
+    let mut filters = HashMap::new();
+    filters.insert("until", vec!["24h"]);
+    let result = &docker
+        .prune_build(Some(PruneBuildOptions {
+            filters,
+            ..Default::default()
+        }))
+        .await?;
+
+    assert!(result.space_reclaimed.is_some());
+
+    Ok(())
+}
+
 async fn remove_image_test(docker: Docker) -> Result<(), Error> {
     let image = if cfg!(windows) {
         format!("{}hello-world:nanoserver", registry_http_addr())
@@ -416,6 +431,99 @@ async fn export_image_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn export_images_test(docker: Docker) -> Result<(), Error> {
+    create_image_hello_world(&docker).await?;
+
+    let hello_world = format!("{}hello-world:linux", registry_http_addr());
+    let alpine = format!("{}alpine:latest", registry_http_addr());
+
+    let _ = &docker
+        .create_image(
+            Some(CreateImageOptions {
+                from_image: &alpine[..],
+                ..Default::default()
+            }),
+            None,
+            Some(integration_test_registry_credentials()),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let res = docker.export_images(&[&hello_world, &alpine]);
+
+    let bytes = concat_byte_stream(res).await?;
+
+    let mut archive = tar::Archive::new(&bytes[..]);
+    let mut manifest = archive
+        .entries()?
+        .map(|entry| entry.unwrap())
+        .find(|entry| entry.path().unwrap().to_str() == Some("manifest.json"))
+        .unwrap();
+
+    let mut contents = String::new();
+    std::io::Read::read_to_string(&mut manifest, &mut contents)?;
+    let manifest: Vec<serde_json::Value> = serde_json::from_str(&contents)?;
+
+    assert_eq!(manifest.len(), 2);
+
+    Ok(())
+}
+
+async fn create_image_from_tarball_test(docker: Docker) -> Result<(), Error> {
+    let contents = r#"Hello from Bollard!"#.as_bytes();
+
+    let mut header = tar::Header::new_gnu();
+    header.set_path("hello.txt").unwrap();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    let mut tar = tar::Builder::new(Vec::new());
+    tar.append(&header, contents).unwrap();
+
+    let rootfs = tar.into_inner().unwrap();
+
+    let result = docker
+        .create_image(
+            Some(CreateImageOptions {
+                from_src: "-",
+                repo: "integration_test_create_image_from_tarball",
+                tag: "latest",
+                changes: Some("CMD [\"/hello\"]\nENV BOLLARD=1"),
+                message: Some("imported by bollard"),
+                ..Default::default()
+            }),
+            Some(rootfs.into()),
+            None,
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    assert!(result.iter().all(|info| info.error.is_none()));
+
+    let image = docker
+        .inspect_image("integration_test_create_image_from_tarball:latest")
+        .await?;
+
+    assert_eq!(image.comment, "imported by bollard");
+    let config = image.config.unwrap();
+    assert_eq!(config.cmd, Some(vec![String::from("/hello")]));
+    assert!(config
+        .env
+        .unwrap()
+        .iter()
+        .any(|env| env.as_str() == "BOLLARD=1"));
+
+    let _ = &docker
+        .remove_image(
+            "integration_test_create_image_from_tarball:latest",
+            None::<RemoveImageOptions>,
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
 async fn issue_55_test(docker: Docker) -> Result<(), Error> {
     let dockerfile = "FROM ubuntu:18.04
 RUN apt-get update && \
@@ -524,6 +632,31 @@ async fn import_image_test(docker: Docker) -> Result<(), Error> {
 
     Ok(())
 }
+async fn resolve_image_digest_test(docker: Docker) -> Result<(), Error> {
+    let image = format!("{}hello-world:linux", registry_http_addr());
+
+    let inspect = docker
+        .inspect_registry_image(&image, Some(integration_test_registry_credentials()))
+        .await?;
+
+    let resolved = docker
+        .resolve_image_digest(&image, Some(integration_test_registry_credentials()))
+        .await?;
+
+    assert!(resolved.digest.starts_with("sha256:"));
+    assert_eq!(Some(&resolved.digest), inspect.descriptor.digest.as_ref());
+    assert_eq!(
+        resolved.reference,
+        format!("{}hello-world@{}", registry_http_addr(), resolved.digest)
+    );
+    assert!(resolved
+        .platforms
+        .iter()
+        .any(|platform| platform.os.as_deref() == Some("linux")));
+
+    Ok(())
+}
+
 // ND - Test sometimes hangs on appveyor.
 #[cfg(not(windows))]
 #[test]
@@ -536,6 +669,12 @@ fn integration_test_create_image() {
     connect_to_docker_and_run!(create_image_test);
 }
 
+#[test]
+#[cfg(unix)]
+fn integration_test_create_image_from_tarball() {
+    connect_to_docker_and_run!(create_image_from_tarball_test);
+}
+
 #[test]
 // ND - Test sometimes hangs on appveyor.
 #[cfg(not(windows))]
@@ -560,6 +699,12 @@ fn integration_test_prune_images() {
     connect_to_docker_and_run!(prune_images_test);
 }
 
+#[test]
+#[cfg(unix)]
+fn integration_test_prune_build() {
+    connect_to_docker_and_run!(prune_build_test);
+}
+
 #[test]
 // ND - Test sometimes hangs on appveyor.
 #[cfg(not(windows))]
@@ -583,6 +728,12 @@ fn integration_test_export_image() {
     connect_to_docker_and_run!(export_image_test);
 }
 
+#[test]
+#[cfg(unix)]
+fn integration_test_export_images() {
+    connect_to_docker_and_run!(export_images_test);
+}
+
 #[test]
 #[cfg(unix)]
 // Flaky
@@ -596,3 +747,9 @@ fn integration_test_issue_55() {
 fn integration_test_import_image() {
     connect_to_docker_and_run!(import_image_test);
 }
+
+#[test]
+#[cfg(unix)]
+fn integration_test_resolve_image_digest() {
+    connect_to_docker_and_run!(resolve_image_digest_test);
+}
@@ -0,0 +1,73 @@
+use bollard::errors::Error;
+use bollard::{node::*, Docker};
+
+use tokio::runtime::Runtime;
+
+use std::collections::HashMap;
+
+#[macro_use]
+mod common;
+use crate::common::*;
+
+async fn node_list_test(docker: Docker) -> Result<(), Error> {
+    let mut filters = HashMap::new();
+    filters.insert("role", vec!["manager"]);
+
+    let nodes = docker
+        .list_nodes(Some(ListNodesOptions { filters }))
+        .await?;
+
+    assert_eq!(nodes.len(), 1);
+
+    Ok(())
+}
+
+async fn node_update_test(docker: Docker) -> Result<(), Error> {
+    let nodes = docker.list_nodes(None::<ListNodesOptions<String>>).await?;
+    let node_id = nodes.first().unwrap().id.as_ref().unwrap().to_owned();
+
+    let node = docker.inspect_node(&node_id).await?;
+    let mut labels = HashMap::new();
+    labels.insert(String::from("maintainer"), String::from("bollard"));
+    let spec = NodeSpec {
+        labels: Some(labels.clone()),
+        ..node.spec.unwrap()
+    };
+    let options = UpdateNodeOptions {
+        version: node.version.unwrap().index.unwrap(),
+    };
+
+    docker.update_node(&node_id, spec.clone(), options).await?;
+
+    let node = docker.inspect_node(&node_id).await?;
+
+    assert_eq!(node.spec.as_ref().unwrap().labels, Some(labels));
+
+    let options = UpdateNodeOptions {
+        version: node.version.unwrap().index.unwrap(),
+    };
+    docker
+        .update_node(
+            &node_id,
+            NodeSpec {
+                labels: None,
+                ..spec
+            },
+            options,
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_list_nodes() {
+    connect_to_docker_and_run!(node_list_test);
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_update_node() {
+    connect_to_docker_and_run!(node_update_test);
+}
@@ -0,0 +1,79 @@
+use bollard::errors::Error;
+use bollard::plugin::*;
+use bollard::Docker;
+
+use futures_util::stream::TryStreamExt;
+use tokio::runtime::Runtime;
+
+use std::collections::HashMap;
+
+#[macro_use]
+mod common;
+use crate::common::*;
+
+async fn install_plugin_test(docker: Docker) -> Result<(), Error> {
+    let privileges = docker.get_plugin_privileges("vieux/sshfs:latest").await?;
+
+    assert!(privileges
+        .iter()
+        .any(|privilege| privilege.name.as_deref() == Some("network")));
+
+    docker
+        .install_plugin(
+            InstallPluginOptions {
+                remote: "vieux/sshfs:latest",
+                name: Some("bollard-sshfs"),
+            },
+            privileges,
+            None,
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let plugin = docker.inspect_plugin("bollard-sshfs").await?;
+
+    assert!(!plugin.enabled);
+
+    docker.set_plugin("bollard-sshfs", vec!["DEBUG=1"]).await?;
+
+    let plugin = docker.inspect_plugin("bollard-sshfs").await?;
+
+    assert!(plugin
+        .settings
+        .env
+        .iter()
+        .any(|env| env.as_str() == "DEBUG=1"));
+
+    docker
+        .enable_plugin("bollard-sshfs", None::<EnablePluginOptions>)
+        .await?;
+
+    let mut filters = HashMap::new();
+    filters.insert("enable", vec!["true"]);
+
+    let plugins = docker
+        .list_plugins(Some(ListPluginsOptions { filters }))
+        .await?;
+
+    assert!(plugins
+        .iter()
+        .any(|plugin| plugin.name == "bollard-sshfs:latest"));
+
+    docker
+        .disable_plugin("bollard-sshfs", None::<DisablePluginOptions>)
+        .await?;
+
+    let plugin = docker
+        .remove_plugin("bollard-sshfs", None::<RemovePluginOptions>)
+        .await?;
+
+    assert_eq!(plugin.name, "bollard-sshfs:latest");
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_install_plugin() {
+    connect_to_docker_and_run!(install_plugin_test);
+}
@@ -0,0 +1,138 @@
+use bollard::errors::Error;
+use bollard::{secret::*, Docker};
+
+use tokio::runtime::Runtime;
+
+use std::collections::HashMap;
+
+#[macro_use]
+mod common;
+use crate::common::*;
+
+async fn secret_create_test(docker: Docker) -> Result<(), Error> {
+    let mut labels = HashMap::new();
+    labels.insert(String::from("secret_create_test"), String::from("bollard"));
+    let spec = SecretSpec {
+        name: Some(String::from("secret_create_test")),
+        labels: Some(labels),
+        ..Default::default()
+    };
+
+    let response = docker
+        .create_secret(spec, Some("a secret".as_bytes()))
+        .await?;
+
+    let secret = docker.inspect_secret(&response.id).await?;
+    let spec = secret.spec.as_ref().unwrap();
+
+    assert_eq!(spec.name.as_ref().unwrap(), "secret_create_test");
+    // unlike a config, the daemon never hands the data of a secret back
+    assert_eq!(spec.data, None);
+
+    let mut filters = HashMap::new();
+    filters.insert("label", vec!["secret_create_test=bollard"]);
+
+    let secrets = docker
+        .list_secrets(Some(ListSecretsOptions { filters }))
+        .await?;
+
+    assert_eq!(secrets.len(), 1);
+    assert_eq!(secrets.first().unwrap().id, secret.id);
+
+    docker.delete_secret(&response.id).await?;
+
+    Ok(())
+}
+
+async fn secret_in_use_test(docker: Docker) -> Result<(), Error> {
+    let spec = SecretSpec {
+        name: Some(String::from("secret_in_use_test")),
+        ..Default::default()
+    };
+
+    let response = docker
+        .create_secret(spec, Some("a secret".as_bytes()))
+        .await?;
+
+    let spec = ServiceSpec {
+        name: Some(String::from("integration_test_secret_in_use")),
+        task_template: Some(TaskSpec {
+            container_spec: Some(TaskSpecContainerSpec {
+                image: Some(format!("{}fussybeaver/uhttpd", registry_http_addr())),
+                secrets: Some(vec![TaskSpecContainerSpecSecrets {
+                    secret_id: Some(response.id.clone()),
+                    secret_name: Some(String::from("secret_in_use_test")),
+                    file: Some(TaskSpecContainerSpecFile {
+                        name: Some(String::from("secret_in_use_test")),
+                        uid: Some(String::from("0")),
+                        gid: Some(String::from("0")),
+                        mode: Some(0o400),
+                    }),
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    docker.create_service(spec, None).await?;
+
+    // a secret referenced by a service cannot be removed
+    assert!(docker.delete_secret(&response.id).await.is_err());
+
+    docker
+        .delete_service("integration_test_secret_in_use")
+        .await?;
+    docker.delete_secret(&response.id).await?;
+
+    Ok(())
+}
+
+async fn secret_update_test(docker: Docker) -> Result<(), Error> {
+    let spec = SecretSpec {
+        name: Some(String::from("secret_update_test")),
+        ..Default::default()
+    };
+
+    let response = docker.create_secret(spec, Some(&b"a secret"[..])).await?;
+
+    let secret = docker.inspect_secret(&response.id).await?;
+    let mut labels = HashMap::new();
+    labels.insert(String::from("maintainer"), String::from("bollard"));
+    let spec = SecretSpec {
+        labels: Some(labels.clone()),
+        ..secret.spec.unwrap()
+    };
+    let options = UpdateSecretOptions {
+        version: secret.version.unwrap().index.unwrap(),
+    };
+
+    docker.update_secret(&response.id, spec, options).await?;
+
+    let secret = docker.inspect_secret(&response.id).await?;
+
+    assert_eq!(secret.spec.unwrap().labels, Some(labels));
+
+    docker.delete_secret(&response.id).await?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_create_secret() {
+    connect_to_docker_and_run!(secret_create_test);
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_secret_in_use() {
+    connect_to_docker_and_run!(secret_in_use_test);
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_update_secret() {
+    connect_to_docker_and_run!(secret_update_test);
+}
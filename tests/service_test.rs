@@ -1,6 +1,7 @@
 use bollard::errors::Error;
 use bollard::{service::*, Docker};
 
+use futures_util::stream::TryStreamExt;
 use tokio::runtime::Runtime;
 
 #[macro_use]
@@ -230,6 +231,70 @@ async fn service_rollback_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn service_logs_test(docker: Docker) -> Result<(), Error> {
+    let spec = ServiceSpec {
+        name: Some(String::from("integration_test_service_logs")),
+        task_template: Some(TaskSpec {
+            container_spec: Some(TaskSpecContainerSpec {
+                image: Some(format!("{}alpine", registry_http_addr())),
+                command: Some(vec![
+                    String::from("/bin/sh"),
+                    String::from("-c"),
+                    String::from("echo integration_test_service_logs && sleep 300"),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let service_id = docker.create_service(spec, None).await?.id.unwrap();
+
+    // The service only has output once a task has started.
+    let mut logs = vec![];
+    for _ in 0..20 {
+        logs = docker
+            .service_logs(
+                "integration_test_service_logs",
+                Some(ServiceLogsOptions {
+                    stdout: true,
+                    stderr: true,
+                    details: true,
+                    timestamps: true,
+                    tail: "all",
+                    ..Default::default()
+                }),
+            )
+            .try_collect::<Vec<_>>()
+            .await?;
+        if !logs.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+
+    docker
+        .delete_service("integration_test_service_logs")
+        .await?;
+
+    assert!(!logs.is_empty());
+    for log in logs {
+        let details = log.details.unwrap();
+        assert!(log.timestamp.is_some());
+        assert_eq!(details.service_id.as_ref(), Some(&service_id));
+        assert_eq!(
+            details.attrs.get("com.docker.swarm.service.id"),
+            Some(&service_id)
+        );
+        assert!(details.task_id.is_some());
+        assert!(details.attrs.contains_key("com.docker.swarm.task.id"));
+        assert_eq!(log.output.to_string(), "integration_test_service_logs\n");
+    }
+
+    Ok(())
+}
+
 #[test]
 #[cfg(unix)]
 fn integration_test_create_service() {
@@ -253,3 +318,9 @@ fn integration_test_update_service() {
 fn integration_test_rollback_service() {
     connect_to_docker_and_run!(service_rollback_test);
 }
+
+#[test]
+#[cfg(unix)]
+fn integration_test_service_logs() {
+    connect_to_docker_and_run!(service_logs_test);
+}
@@ -0,0 +1,131 @@
+use bollard::errors::Error;
+use bollard::models::*;
+use bollard::{swarm::*, Docker};
+
+use futures_util::future::FutureExt;
+use tokio::runtime::Runtime;
+
+use std::panic::{resume_unwind, AssertUnwindSafe};
+
+#[macro_use]
+mod common;
+use crate::common::*;
+
+async fn swarm_inspect_test(docker: Docker) -> Result<(), Error> {
+    let swarm = docker.inspect_swarm().await?;
+
+    assert!(swarm.id.is_some());
+    assert!(swarm.join_tokens.unwrap().worker.is_some());
+
+    Ok(())
+}
+
+async fn swarm_update_rotate_token_test(docker: Docker) -> Result<(), Error> {
+    let swarm = docker.inspect_swarm().await?;
+    let worker_token = swarm.join_tokens.unwrap().worker;
+
+    let options = UpdateSwarmOptions {
+        version: swarm.version.unwrap().index.unwrap(),
+        rotate_worker_token: true,
+        ..Default::default()
+    };
+
+    docker.update_swarm(swarm.spec.unwrap(), options).await?;
+
+    let swarm = docker.inspect_swarm().await?;
+
+    assert_ne!(swarm.join_tokens.unwrap().worker, worker_token);
+
+    Ok(())
+}
+
+async fn swarm_init_leave_test(docker: Docker) -> Result<(), Error> {
+    // The integration tests run against a daemon that is already part of a swarm, which the suites
+    // that follow rely on, so a swarm is restored however this test ends.
+    let result = AssertUnwindSafe(swarm_init_leave(&docker))
+        .catch_unwind()
+        .await;
+    let restored = restore_swarm(&docker).await;
+
+    match result {
+        Ok(result) => result.and(restored),
+        Err(panic) => resume_unwind(panic),
+    }
+}
+
+async fn restore_swarm(docker: &Docker) -> Result<(), Error> {
+    let _ = docker
+        .leave_swarm(Some(LeaveSwarmOptions { force: true }))
+        .await;
+
+    docker
+        .init_swarm(InitSwarmOptions {
+            listen_addr: "0.0.0.0:2377",
+            advertise_addr: "127.0.0.1",
+            ..Default::default()
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn swarm_init_leave(docker: &Docker) -> Result<(), Error> {
+    let _ = docker
+        .leave_swarm(Some(LeaveSwarmOptions { force: true }))
+        .await;
+
+    let node_id = docker
+        .init_swarm(InitSwarmOptions {
+            listen_addr: "0.0.0.0:2377",
+            advertise_addr: "127.0.0.1",
+            spec: SwarmSpec {
+                encryption_config: Some(SwarmSpecEncryptionConfig {
+                    auto_lock_managers: Some(true),
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await?;
+    assert!(!node_id.is_empty());
+
+    let swarm = docker.inspect_swarm().await?;
+    assert!(swarm.id.is_some());
+    assert_eq!(
+        swarm
+            .spec
+            .unwrap()
+            .encryption_config
+            .unwrap()
+            .auto_lock_managers,
+        Some(true)
+    );
+
+    let unlock_key = docker.get_unlock_key().await?;
+    assert!(unlock_key.unlock_key.unwrap().starts_with("SWMKEY-"));
+
+    docker
+        .leave_swarm(Some(LeaveSwarmOptions { force: true }))
+        .await?;
+    assert!(docker.inspect_swarm().await.is_err());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_init_leave_swarm() {
+    connect_to_docker_and_run!(swarm_init_leave_test);
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_inspect_swarm() {
+    connect_to_docker_and_run!(swarm_inspect_test);
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_update_swarm() {
+    connect_to_docker_and_run!(swarm_update_rotate_token_test);
+}
@@ -160,6 +160,22 @@ async fn ping_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn check_auth_test(docker: Docker) -> Result<(), Error> {
+    let serveraddress = registry_http_addr();
+    let serveraddress = serveraddress.trim_end_matches('/');
+
+    let response = docker
+        .check_auth(DockerCredentials {
+            serveraddress: Some(String::from(serveraddress)),
+            ..integration_test_registry_credentials()
+        })
+        .await?;
+
+    assert_eq!(response.status, "Login Succeeded");
+
+    Ok(())
+}
+
 #[test]
 fn integration_test_events() {
     connect_to_docker_and_run!(events_test);
@@ -186,3 +202,9 @@ fn integration_test_info() {
 fn integration_test_ping() {
     connect_to_docker_and_run!(ping_test);
 }
+
+#[test]
+#[cfg(unix)]
+fn integration_test_check_auth() {
+    connect_to_docker_and_run!(check_auth_test);
+}
@@ -0,0 +1,129 @@
+use bollard::errors::Error;
+use bollard::service::{ServiceSpec, TaskSpec, TaskSpecContainerSpec};
+use bollard::{task::*, Docker};
+
+use futures_util::stream::TryStreamExt;
+use tokio::runtime::Runtime;
+
+use std::collections::HashMap;
+
+#[macro_use]
+mod common;
+use crate::common::*;
+
+async fn list_service_tasks(docker: &Docker, service_name: &str) -> Result<Vec<Task>, Error> {
+    let mut filters = HashMap::new();
+    filters.insert("service", vec![service_name]);
+
+    for _ in 0..20 {
+        let tasks = docker
+            .list_tasks(Some(ListTasksOptions {
+                filters: filters.clone(),
+            }))
+            .await?;
+        if !tasks.is_empty() {
+            return Ok(tasks);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+
+    Ok(vec![])
+}
+
+async fn task_list_inspect_test(docker: Docker) -> Result<(), Error> {
+    let image = if cfg!(windows) {
+        format!("{}nanoserver/iis", registry_http_addr())
+    } else {
+        format!("{}fussybeaver/uhttpd", registry_http_addr())
+    };
+    let spec = ServiceSpec {
+        name: Some(String::from("integration_test_list_tasks")),
+        task_template: Some(TaskSpec {
+            container_spec: Some(TaskSpecContainerSpec {
+                image: Some(image),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    docker.create_service(spec, None).await?;
+
+    let tasks = list_service_tasks(&docker, "integration_test_list_tasks").await?;
+
+    assert_eq!(tasks.len(), 1);
+
+    let task_id = tasks.first().unwrap().id.as_ref().unwrap();
+    let task = docker.inspect_task(task_id).await?;
+
+    assert_eq!(task.id.as_ref(), Some(task_id));
+
+    docker.delete_service("integration_test_list_tasks").await?;
+
+    Ok(())
+}
+
+async fn task_logs_test(docker: Docker) -> Result<(), Error> {
+    let spec = ServiceSpec {
+        name: Some(String::from("integration_test_task_logs")),
+        task_template: Some(TaskSpec {
+            container_spec: Some(TaskSpecContainerSpec {
+                image: Some(format!("{}alpine", registry_http_addr())),
+                command: Some(vec![
+                    String::from("/bin/sh"),
+                    String::from("-c"),
+                    String::from("echo integration_test_task_logs && sleep 300"),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    docker.create_service(spec, None).await?;
+
+    let tasks = list_service_tasks(&docker, "integration_test_task_logs").await?;
+    let task_id = tasks.first().unwrap().id.as_ref().unwrap();
+
+    // The task only has output once its container has started.
+    let mut logs = vec![];
+    for _ in 0..20 {
+        logs = docker
+            .task_logs(
+                task_id,
+                Some(TaskLogsOptions::<String> {
+                    stdout: true,
+                    stderr: true,
+                    ..Default::default()
+                }),
+            )
+            .try_collect::<Vec<_>>()
+            .await?;
+        if !logs.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+
+    docker.delete_service("integration_test_task_logs").await?;
+
+    assert!(logs
+        .iter()
+        .any(|log| log.to_string().contains("integration_test_task_logs")));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_list_tasks() {
+    connect_to_docker_and_run!(task_list_inspect_test);
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_task_logs() {
+    connect_to_docker_and_run!(task_logs_test);
+}